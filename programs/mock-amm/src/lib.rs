@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+declare_id!("MockAmm111111111111111111111111111111111111");
+
+/// Minimal constant-product AMM for testing `MigrationTarget::Cpmm`
+///
+/// Implements only the `initialize_pool` instruction the `Cpmm` adapter
+/// calls at `complete`, with the account order documented in
+/// `pump_fun_clone::migration::Cpmm`. Not meant for deployment.
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    /// Create a pool seeded with `sol_amount` lamports and `token_amount`
+    /// tokens, minting isqrt(sol_amount * token_amount) LP tokens to `lp_owner`
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        sol_amount: u64,
+        token_amount: u64,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+
+        // Pull the tokens from the depositor
+        let cpi_accounts = token_2022::TransferChecked {
+            from: accounts.token_source.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.token_vault.to_account_info(),
+            authority: accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, token_amount, accounts.mint.decimals)?;
+
+        // Pull the SOL into the pool account
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accounts.sol_source.to_account_info(),
+                    to: accounts.pool.to_account_info(),
+                },
+            ),
+            sol_amount,
+        )?;
+
+        let lp_supply = isqrt((sol_amount as u128) * (token_amount as u128));
+        require!(lp_supply > 0, MockAmmError::ZeroLiquidity);
+
+        let pool = &mut accounts.pool;
+        pool.mint = accounts.mint.key();
+        pool.lp_mint = accounts.lp_mint.key();
+        pool.sol_reserve = sol_amount;
+        pool.token_reserve = token_amount;
+        pool.lp_supply = lp_supply;
+        pool.bump = ctx.bumps.pool;
+
+        let mint_key = pool.mint;
+        let seeds = &[
+            b"pool",
+            mint_key.as_ref(),
+            &[pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: accounts.lp_mint.to_account_info(),
+            to: accounts.lp_account.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022::mint_to(cpi_ctx, lp_supply)
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub depositor: Signer<'info>,
+
+    #[account(mut)]
+    pub sol_source: Signer<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = depositor,
+    )]
+    pub token_source: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Only used as the owner of the LP token account
+    pub lp_owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = AmmPool::SIZE,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, AmmPool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct AmmPool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    pub lp_supply: u64,
+    pub bump: u8,
+}
+
+impl AmmPool {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // mint
+        32 + // lp_mint
+        8 +  // sol_reserve
+        8 +  // token_reserve
+        8 +  // lp_supply
+        1;   // bump
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Initial liquidity is zero")]
+    ZeroLiquidity,
+}
+
+fn isqrt(value: u128) -> u64 {
+    if value < 2 {
+        return value as u64;
+    }
+    let mut x = value;
+    let mut y = value / 2 + (value & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x as u64
+}
@@ -1,41 +1,143 @@
 use anchor_lang::prelude::*;
 use crate::errors::PumpFunError;
+use crate::state::CurveParams;
+
+/// Token decimals for every mint created by the program
+pub const TOKEN_DECIMALS: u8 = 6;
 
 /// Total token supply: 1 billion tokens with 6 decimals
+/// Minted once into the curve's token account at create; mint authority is then revoked
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B * 10^6
 
-/// Tokens available on bonding curve (793M tokens)
+/// Default tokens available on a bonding curve (793M tokens)
 /// Remaining tokens are reserved for liquidity pool
 pub const CURVE_TOKENS: u64 = 793_000_000_000_000; // 793M * 10^6
 
 /// Reserved tokens for liquidity pool (207M tokens)
 pub const RESERVED_TOKENS: u64 = 207_000_000_000_000; // 207M * 10^6
 
-/// Initial virtual SOL reserve (starting point for bonding curve)
+/// Default initial virtual SOL reserve (starting point for bonding curve)
 /// This determines the starting price
 pub const INITIAL_VIRTUAL_SOL_RESERVE: u64 = 30_000_000_000; // 30 SOL in lamports
 
-/// Initial virtual token reserve (starting point for bonding curve)
+/// Default initial virtual token reserve (starting point for bonding curve)
 /// This is set to match CURVE_TOKENS initially
 pub const INITIAL_VIRTUAL_TOKEN_RESERVE: u64 = CURVE_TOKENS;
 
-/// Target virtual market cap at graduation (in lamports)
-/// ~69k-100k USD equivalent, using ~$150/SOL = ~460-666 SOL
-/// We use 500 SOL as a reasonable target
-pub const TARGET_VIRTUAL_MC: u64 = 500_000_000_000; // 500 SOL in lamports
+/// Default graduation threshold: real SOL raised by the curve (in lamports)
+/// Matches the ~85 SOL pump.fun curves raise before migrating
+pub const DEFAULT_GRADUATION_THRESHOLD: u64 = 85_000_000_000; // 85 SOL in lamports
+
+/// Standard launch tier, matching the defaults above
+pub const DEFAULT_CURVE_PARAMS: CurveParams = CurveParams {
+    initial_virtual_sol_reserve: INITIAL_VIRTUAL_SOL_RESERVE,
+    initial_virtual_token_reserve: INITIAL_VIRTUAL_TOKEN_RESERVE,
+    graduation_threshold: DEFAULT_GRADUATION_THRESHOLD,
+    token_allocation: CURVE_TOKENS,
+};
+
+/// Default lower bounds for per-curve parameters chosen at `create`
+pub const DEFAULT_MIN_CURVE_PARAMS: CurveParams = CurveParams {
+    initial_virtual_sol_reserve: 1_000_000_000,        // 1 SOL
+    initial_virtual_token_reserve: 10_000_000_000_000, // 10M tokens
+    graduation_threshold: 1_000_000_000,               // 1 SOL
+    token_allocation: 10_000_000_000_000,              // 10M tokens
+};
 
-/// Protocol fee basis points (0.5% = 50 bps)
+/// Default upper bounds for per-curve parameters chosen at `create`
+pub const DEFAULT_MAX_CURVE_PARAMS: CurveParams = CurveParams {
+    initial_virtual_sol_reserve: 1_000_000_000_000, // 1,000 SOL
+    initial_virtual_token_reserve: TOTAL_SUPPLY,
+    graduation_threshold: 100_000_000_000_000,      // 100,000 SOL
+    token_allocation: TOTAL_SUPPLY,
+};
+
+/// Default protocol fee basis points (0.5% = 50 bps)
+/// The effective fee is read from `GlobalConfig.protocol_fee_bps` on every trade
 pub const PROTOCOL_FEE_BPS: u16 = 50;
 
+/// Maximum protocol fee the authority can configure (10% = 1000 bps)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+/// Default swap fee of graduation pools (0.25% = 25 bps)
+/// Kept in the pool's reserves, so it accrues to liquidity providers
+pub const DEFAULT_POOL_FEE_BPS: u16 = 25;
+
+/// Maximum pool swap fee the authority can configure (10% = 1000 bps)
+pub const MAX_POOL_FEE_BPS: u16 = 1_000;
+
+/// Default shortest LP lock a creator can choose at `create` (30 days)
+pub const DEFAULT_MIN_LP_LOCK_DURATION: i64 = 30 * 24 * 60 * 60;
+
+/// Default fee taken from a curve's real SOL reserve at `complete` (0.05 SOL)
+pub const DEFAULT_MIGRATION_FEE: u64 = 50_000_000;
+
+/// Maximum migration fee the authority can configure (6 SOL, as on pump.fun)
+pub const MAX_MIGRATION_FEE: u64 = 6_000_000_000;
+
+/// Default part of the migration fee paid to the caller of `complete` (0.02 SOL)
+/// Covers the rent the completer pays for the accounts created at graduation
+pub const DEFAULT_COMPLETER_REWARD: u64 = 20_000_000;
+
 /// Token creation fee (0.02 SOL)
 pub const CREATION_FEE: u64 = 20_000_000; // 0.02 SOL in lamports
 
+/// Maximum creation fee the authority can configure (1 SOL)
+pub const MAX_CREATION_FEE: u64 = 1_000_000_000; // 1 SOL in lamports
+
+/// Default maximum token name length in bytes
+pub const MAX_NAME_LEN: u16 = 32;
+
+/// Default maximum token symbol length in bytes
+pub const MAX_SYMBOL_LEN: u16 = 10;
+
+/// Default maximum metadata URI length in bytes
+pub const MAX_URI_LEN: u16 = 200;
+
+/// Upper bounds the authority can configure for the metadata length limits
+pub const NAME_LEN_LIMIT: u16 = 64;
+pub const SYMBOL_LEN_LIMIT: u16 = 16;
+pub const URI_LEN_LIMIT: u16 = 512;
+
+/// URI schemes accepted for token metadata
+pub const ALLOWED_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+
 /// Minimum SOL amount for buy/sell operations (0.001 SOL)
 pub const MIN_SOL_AMOUNT: u64 = 1_000_000; // 0.001 SOL
 
 /// Slippage tolerance basis points (5% default)
 pub const DEFAULT_SLIPPAGE_BPS: u16 = 500;
 
+/// Fixed-point scale for quoted spot prices (lamports per whole token * 1e9)
+pub const PRICE_SCALE: u128 = 1_000_000_000;
+
+/// Divides `price * base units` back to lamports (10^TOKEN_DECIMALS * PRICE_SCALE)
+pub const PRICE_DENOMINATOR: u128 = 10u128.pow(TOKEN_DECIMALS as u32) * PRICE_SCALE;
+
+/// Number of equal price steps on linear and exponential curves
+/// Each step covers `token_allocation / CURVE_STEPS` tokens; the last step
+/// also takes the remainder
+pub const CURVE_STEPS: u64 = 100;
+
+/// `GlobalConfig.enabled_curve_kinds` bit for constant-product curves
+pub const CURVE_KIND_CONSTANT_PRODUCT: u8 = 1 << 0;
+
+/// `GlobalConfig.enabled_curve_kinds` bit for linear curves
+pub const CURVE_KIND_LINEAR: u8 = 1 << 1;
+
+/// `GlobalConfig.enabled_curve_kinds` bit for exponential curves
+pub const CURVE_KIND_EXPONENTIAL: u8 = 1 << 2;
+
+/// Every curve kind the program supports
+pub const ALL_CURVE_KINDS: u8 =
+    CURVE_KIND_CONSTANT_PRODUCT | CURVE_KIND_LINEAR | CURVE_KIND_EXPONENTIAL;
+
+/// Default lowest start price for linear/exponential curves (1 lamport per token)
+pub const DEFAULT_MIN_START_PRICE: u64 = 1_000_000_000;
+
+/// Default highest end price for linear/exponential curves (0.001 SOL per token)
+pub const DEFAULT_MAX_END_PRICE: u64 = 1_000_000_000_000_000;
+
 /// Calculate the constant product k = x * y
 /// where x = virtual SOL reserve, y = virtual token reserve
 #[inline]
@@ -43,10 +145,67 @@ pub fn calculate_k(sol_reserve: u64, token_reserve: u64) -> u128 {
     (sol_reserve as u128) * (token_reserve as u128)
 }
 
+/// Divide rounding down
+/// Used wherever the result is paid out by the pool, so the pool never
+/// gives away a fractional unit
+#[inline]
+pub fn div_floor(numerator: u128, denominator: u128) -> Result<u128> {
+    require!(denominator > 0, PumpFunError::MathOverflow);
+    Ok(numerator / denominator)
+}
+
+/// Divide rounding up
+/// Used wherever the result is owed to the pool or protocol (post-trade
+/// reserves, required inputs, fees), so any fractional unit stays with them
+#[inline]
+pub fn div_ceil(numerator: u128, denominator: u128) -> Result<u128> {
+    require!(denominator > 0, PumpFunError::MathOverflow);
+    Ok(numerator.div_ceil(denominator))
+}
+
+/// Narrow an intermediate u128 result back to u64
+#[inline]
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| PumpFunError::MathOverflow.into())
+}
+
+/// Calculate the protocol fee on an amount
+/// Formula: fee = ceil(amount * fee_bps / 10000)
+/// Rounded up so splitting a trade into dust-sized pieces cannot avoid the fee
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = div_ceil(
+        (amount as u128)
+            .checked_mul(fee_bps as u128)
+            .ok_or(PumpFunError::MathOverflow)?,
+        10000,
+    )?;
+
+    to_u64(fee)
+}
+
+/// Split an amount into (protocol fee, remainder)
+/// The fee is the only protocol fee on a trade and is always taken in SOL:
+/// from `sol_in` before it reaches the curve on a buy, and from the curve's
+/// SOL output on a sell.
+pub fn split_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    let fee = calculate_fee(amount, fee_bps)?;
+    let remainder = amount
+        .checked_sub(fee)
+        .ok_or(PumpFunError::MathOverflow)?;
+
+    Ok((fee, remainder))
+}
+
 /// Calculate tokens out given SOL in using constant product formula
-/// Formula: tokens_out = (token_reserve * sol_in * (10000 - fee_bps)) / ((sol_reserve + sol_in) * 10000)
-/// This maintains k = (sol_reserve + sol_in) * (token_reserve - tokens_out)
-pub fn calculate_tokens_out(sol_in: u64, sol_reserve: u64, token_reserve: u64) -> Result<u64> {
+/// Formula: tokens_out = token_reserve - ceil(k / (sol_reserve + sol_in))
+/// Rounding the new token reserve up keeps
+/// (sol_reserve + sol_in) * (token_reserve - tokens_out) >= k
+/// `sol_in` is the amount reaching the curve, i.e. after the protocol fee
+pub fn calculate_tokens_out(
+    sol_in: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+) -> Result<u64> {
     require!(sol_in > 0, PumpFunError::InvalidAmount);
     require!(sol_reserve > 0, PumpFunError::InvalidReserves);
     require!(token_reserve > 0, PumpFunError::InvalidReserves);
@@ -54,31 +213,70 @@ pub fn calculate_tokens_out(sol_in: u64, sol_reserve: u64, token_reserve: u64) -
     let k = calculate_k(sol_reserve, token_reserve);
     let new_sol_reserve = sol_reserve.checked_add(sol_in).ok_or(PumpFunError::MathOverflow)?;
     
-    // Calculate new token reserve: k / new_sol_reserve
-    let new_token_reserve = (k / (new_sol_reserve as u128)) as u64;
+    // Calculate new token reserve: ceil(k / new_sol_reserve)
+    let new_token_reserve = to_u64(div_ceil(k, new_sol_reserve as u128)?)?;
     
     // Tokens out = old reserve - new reserve
     let tokens_out = token_reserve
         .checked_sub(new_token_reserve)
         .ok_or(PumpFunError::InsufficientLiquidity)?;
 
-    // Apply protocol fee: reduce tokens out by fee percentage
-    let fee_amount = (tokens_out as u128)
-        .checked_mul(PROTOCOL_FEE_BPS as u128)
-        .ok_or(PumpFunError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(PumpFunError::MathOverflow)?;
-    
-    let tokens_out_after_fee = tokens_out
-        .checked_sub(fee_amount as u64)
+    Ok(tokens_out)
+}
+
+/// Calculate the SOL that must reach the curve to buy exactly `tokens_out`
+/// Inverse of `calculate_tokens_out`, rounded up in the protocol's favour:
+/// sol_in = ceil(k / (token_reserve - tokens_out)) - sol_reserve
+/// so that (sol_reserve + sol_in) * (token_reserve - tokens_out) >= k
+pub fn calculate_sol_in(
+    tokens_out: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+) -> Result<u64> {
+    require!(tokens_out > 0, PumpFunError::InvalidAmount);
+    require!(sol_reserve > 0, PumpFunError::InvalidReserves);
+    require!(token_reserve > 0, PumpFunError::InvalidReserves);
+    require!(tokens_out < token_reserve, PumpFunError::InsufficientLiquidity);
+
+    let k = calculate_k(sol_reserve, token_reserve);
+    let new_token_reserve = token_reserve - tokens_out;
+
+    // Calculate new SOL reserve: ceil(k / new_token_reserve)
+    let new_sol_reserve = to_u64(div_ceil(k, new_token_reserve as u128)?)?;
+
+    // SOL in = new reserve - old reserve
+    let sol_in = new_sol_reserve
+        .checked_sub(sol_reserve)
         .ok_or(PumpFunError::MathOverflow)?;
 
-    Ok(tokens_out_after_fee)
+    Ok(sol_in)
+}
+
+/// Calculate the gross amount whose remainder after `split_fee` covers `net`
+/// Formula: gross = ceil(net * 10000 / (10000 - fee_bps))
+pub fn calculate_gross_for_net(net: u64, fee_bps: u16) -> Result<u64> {
+    require!(fee_bps < 10000, PumpFunError::InvalidProtocolFee);
+
+    let gross = div_ceil(
+        (net as u128)
+            .checked_mul(10000)
+            .ok_or(PumpFunError::MathOverflow)?,
+        (10000 - fee_bps) as u128,
+    )?;
+
+    to_u64(gross)
 }
 
 /// Calculate SOL out given tokens in using constant product formula
-/// Formula: sol_out = (sol_reserve * tokens_in * (10000 - fee_bps)) / ((token_reserve + tokens_in) * 10000)
-pub fn calculate_sol_out(tokens_in: u64, sol_reserve: u64, token_reserve: u64) -> Result<u64> {
+/// Formula: sol_out = sol_reserve - ceil(k / (token_reserve + tokens_in))
+/// Rounding the new SOL reserve up keeps
+/// (sol_reserve - sol_out) * (token_reserve + tokens_in) >= k
+/// The result is the amount leaving the curve, before the protocol fee
+pub fn calculate_sol_out(
+    tokens_in: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+) -> Result<u64> {
     require!(tokens_in > 0, PumpFunError::InvalidAmount);
     require!(sol_reserve > 0, PumpFunError::InvalidReserves);
     require!(token_reserve > 0, PumpFunError::InvalidReserves);
@@ -88,30 +286,133 @@ pub fn calculate_sol_out(tokens_in: u64, sol_reserve: u64, token_reserve: u64) -
         .checked_add(tokens_in)
         .ok_or(PumpFunError::MathOverflow)?;
     
-    // Calculate new SOL reserve: k / new_token_reserve
-    let new_sol_reserve = (k / (new_token_reserve as u128)) as u64;
+    // Calculate new SOL reserve: ceil(k / new_token_reserve)
+    let new_sol_reserve = to_u64(div_ceil(k, new_token_reserve as u128)?)?;
     
     // SOL out = old reserve - new reserve
     let sol_out = sol_reserve
         .checked_sub(new_sol_reserve)
         .ok_or(PumpFunError::InsufficientLiquidity)?;
 
-    // Apply protocol fee: reduce SOL out by fee percentage
-    let fee_amount = (sol_out as u128)
-        .checked_mul(PROTOCOL_FEE_BPS as u128)
-        .ok_or(PumpFunError::MathOverflow)?
-        .checked_div(10000)
+    Ok(sol_out)
+}
+
+/// Calculate the tokens that must be sold for the curve to release `sol_out`
+/// Inverse of `calculate_sol_out`, rounded up in the protocol's favour:
+/// tokens_in = ceil(k / (sol_reserve - sol_out)) - token_reserve
+/// so that (sol_reserve - sol_out) * (token_reserve + tokens_in) >= k
+pub fn calculate_tokens_in(
+    sol_out: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+) -> Result<u64> {
+    require!(sol_out > 0, PumpFunError::InvalidAmount);
+    require!(sol_reserve > 0, PumpFunError::InvalidReserves);
+    require!(token_reserve > 0, PumpFunError::InvalidReserves);
+    require!(sol_out < sol_reserve, PumpFunError::InsufficientLiquidity);
+
+    let k = calculate_k(sol_reserve, token_reserve);
+    let new_sol_reserve = sol_reserve - sol_out;
+
+    // Calculate new token reserve: ceil(k / new_sol_reserve)
+    let new_token_reserve = to_u64(div_ceil(k, new_sol_reserve as u128)?)?;
+
+    // Tokens in = new reserve - old reserve
+    let tokens_in = new_token_reserve
+        .checked_sub(token_reserve)
         .ok_or(PumpFunError::MathOverflow)?;
-    
-    let sol_out_after_fee = sol_out
-        .checked_sub(fee_amount as u64)
+
+    Ok(tokens_in)
+}
+
+/// Calculate the tokens to buy so the spot price reaches at least `price`
+/// Estimates the new token reserve from price = k * PRICE_DENOMINATOR / y'^2,
+/// then steps to the smallest amount whose exact-output buy (`calculate_sol_in`)
+/// leaves a spot price >= `price`. `None` if the reserve runs out first.
+pub fn calculate_tokens_to_price(
+    price: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+) -> Result<Option<u64>> {
+    let spot_after = |tokens: u64| -> Result<u64> {
+        if tokens == 0 {
+            return calculate_spot_price(sol_reserve, token_reserve);
+        }
+        let sol_in = calculate_sol_in(tokens, sol_reserve, token_reserve)?;
+        let new_sol_reserve = sol_reserve
+            .checked_add(sol_in)
+            .ok_or(PumpFunError::MathOverflow)?;
+        calculate_spot_price(new_sol_reserve, token_reserve - tokens)
+    };
+
+    if spot_after(0)? >= price {
+        return Ok(Some(0));
+    }
+    if token_reserve < 2 {
+        return Ok(None);
+    }
+
+    // y'^2 = x * PRICE_DENOMINATOR / price * y
+    let reserve_squared = div_floor(
+        (sol_reserve as u128)
+            .checked_mul(PRICE_DENOMINATOR)
+            .ok_or(PumpFunError::MathOverflow)?,
+        price as u128,
+    )?
+    .checked_mul(token_reserve as u128)
+    .ok_or(PumpFunError::MathOverflow)?;
+    let estimate = to_u64(isqrt(reserve_squared))?;
+
+    let max_tokens = token_reserve - 1;
+    let mut tokens = token_reserve.saturating_sub(estimate).clamp(1, max_tokens);
+    while spot_after(tokens)? < price {
+        if tokens == max_tokens {
+            return Ok(None);
+        }
+        tokens += 1;
+    }
+    while tokens > 1 && spot_after(tokens - 1)? >= price {
+        tokens -= 1;
+    }
+
+    Ok(Some(tokens))
+}
+
+/// Integer square root, rounded down (Newton's method)
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = value / 2 + (value & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Calculate the spot price of one whole token in lamports, scaled by `PRICE_SCALE`
+/// Formula: price = sol_reserve * PRICE_DENOMINATOR / token_reserve
+pub fn calculate_spot_price(sol_reserve: u64, token_reserve: u64) -> Result<u64> {
+    require!(token_reserve > 0, PumpFunError::InvalidReserves);
+
+    let price = (sol_reserve as u128)
+        .checked_mul(PRICE_DENOMINATOR)
         .ok_or(PumpFunError::MathOverflow)?;
 
-    Ok(sol_out_after_fee)
+    to_u64(div_floor(price, token_reserve as u128)?)
 }
 
-/// Check if bonding curve has reached completion threshold
-/// Completion happens when virtual SOL reserve reaches target market cap
-pub fn is_complete(sol_reserve: u64) -> bool {
-    sol_reserve >= TARGET_VIRTUAL_MC
+/// Calculate the relative change between two spot prices in basis points
+/// Formula: impact = |price_after - price_before| * 10000 / price_before
+pub fn calculate_price_impact_bps(price_before: u64, price_after: u64) -> Result<u64> {
+    require!(price_before > 0, PumpFunError::InvalidReserves);
+
+    let impact = (price_after.abs_diff(price_before) as u128)
+        .checked_mul(10000)
+        .ok_or(PumpFunError::MathOverflow)?;
+
+    to_u64(div_floor(impact, price_before as u128)?)
 }
+
@@ -0,0 +1,401 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::PumpFunError;
+
+/// Price discovery profile of a bonding curve, chosen at `create`
+///
+/// Prices are in lamports per whole token scaled by `PRICE_SCALE`, the same
+/// unit `quote_price` reports.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveKind {
+    /// x * y = k over the curve's virtual reserves
+    ConstantProduct,
+    /// Price rises in equal increments from `start_price` to `end_price`
+    /// across `CURVE_STEPS` equal slices of the token allocation
+    Linear { start_price: u64, end_price: u64 },
+    /// Price starts at `start_price` and grows by `growth_bps` on each of
+    /// `CURVE_STEPS` equal slices of the token allocation
+    Exponential { start_price: u64, growth_bps: u16 },
+}
+
+impl CurveKind {
+    /// Serialized size of the largest variant
+    pub const SIZE: usize = 1 + // variant tag
+        8 + // start_price
+        8;  // end_price / growth_bps
+
+    /// Bit identifying this kind in `GlobalConfig.enabled_curve_kinds`
+    pub fn bit(&self) -> u8 {
+        match self {
+            CurveKind::ConstantProduct => CURVE_KIND_CONSTANT_PRODUCT,
+            CurveKind::Linear { .. } => CURVE_KIND_LINEAR,
+            CurveKind::Exponential { .. } => CURVE_KIND_EXPONENTIAL,
+        }
+    }
+
+    /// Price of the last step for stepped kinds, `None` for constant product
+    pub fn end_price(&self) -> Result<Option<u64>> {
+        match *self {
+            CurveKind::ConstantProduct => Ok(None),
+            CurveKind::Linear { end_price, .. } => Ok(Some(end_price)),
+            CurveKind::Exponential { start_price, growth_bps } => {
+                exponential_step_price(start_price, growth_bps, CURVE_STEPS - 1).map(Some)
+            }
+        }
+    }
+
+    /// Pricing for this kind at the given curve state
+    pub fn pricing(
+        &self,
+        virtual_sol_reserve: u64,
+        virtual_token_reserve: u64,
+        tokens_sold: u64,
+        token_allocation: u64,
+    ) -> Box<dyn CurvePricing> {
+        match *self {
+            CurveKind::ConstantProduct => Box::new(ConstantProduct {
+                sol_reserve: virtual_sol_reserve,
+                token_reserve: virtual_token_reserve,
+            }),
+            CurveKind::Linear { start_price, end_price } => Box::new(Linear {
+                start_price,
+                end_price,
+                tokens_sold,
+                token_allocation,
+            }),
+            CurveKind::Exponential { start_price, growth_bps } => Box::new(Exponential {
+                start_price,
+                growth_bps,
+                tokens_sold,
+                token_allocation,
+            }),
+        }
+    }
+}
+
+/// Common quote interface implemented by every curve kind
+///
+/// All SOL amounts are what reaches or leaves the curve, i.e. exclusive of
+/// the protocol fee. Every method rounds in the pool's favour.
+pub trait CurvePricing {
+    /// Tokens received for `sol_in` lamports (exact-input buy)
+    fn tokens_out(&self, sol_in: u64) -> Result<u64>;
+    /// Lamports required to receive exactly `tokens_out` (exact-output buy)
+    fn sol_in(&self, tokens_out: u64) -> Result<u64>;
+    /// Lamports released for `tokens_in` tokens (exact-input sell)
+    fn sol_out(&self, tokens_in: u64) -> Result<u64>;
+    /// Tokens required to release exactly `sol_out` lamports (exact-output sell)
+    fn tokens_in(&self, sol_out: u64) -> Result<u64>;
+    /// Current spot price, scaled by `PRICE_SCALE`
+    fn spot_price(&self) -> Result<u64>;
+    /// Fewest tokens to buy (exact-output) so the spot price reaches at least
+    /// `price`, or `None` if it never does
+    fn tokens_to_price(&self, price: u64) -> Result<Option<u64>>;
+}
+
+/// Constant product pricing over virtual reserves (see `constants.rs`)
+pub struct ConstantProduct {
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+}
+
+impl CurvePricing for ConstantProduct {
+    fn tokens_out(&self, sol_in: u64) -> Result<u64> {
+        calculate_tokens_out(sol_in, self.sol_reserve, self.token_reserve)
+    }
+
+    fn sol_in(&self, tokens_out: u64) -> Result<u64> {
+        calculate_sol_in(tokens_out, self.sol_reserve, self.token_reserve)
+    }
+
+    fn sol_out(&self, tokens_in: u64) -> Result<u64> {
+        calculate_sol_out(tokens_in, self.sol_reserve, self.token_reserve)
+    }
+
+    fn tokens_in(&self, sol_out: u64) -> Result<u64> {
+        calculate_tokens_in(sol_out, self.sol_reserve, self.token_reserve)
+    }
+
+    fn spot_price(&self) -> Result<u64> {
+        calculate_spot_price(self.sol_reserve, self.token_reserve)
+    }
+
+    fn tokens_to_price(&self, price: u64) -> Result<Option<u64>> {
+        calculate_tokens_to_price(price, self.sol_reserve, self.token_reserve)
+    }
+}
+
+/// Pricing defined by a fixed price per step of the token allocation
+///
+/// Trades walk the steps between the current `tokens_sold` and the target,
+/// accumulating `price * tokens` exactly in u128 and dividing by
+/// `PRICE_DENOMINATOR` once at the end. The walk prices its first step with
+/// `step_price` and carries the price from step to step with
+/// `next_step_price`, so a trade costs O(CURVE_STEPS) whatever the schedule.
+pub trait StepSchedule {
+    /// Price of step `step` (0-based, below `CURVE_STEPS`)
+    fn step_price(&self, step: u64) -> Result<u64>;
+    /// Price of step `step + 1`, given `price`, the price of `step`
+    fn next_step_price(&self, step: u64, price: u64) -> Result<u64>;
+    /// Tokens sold so far on this curve
+    fn tokens_sold(&self) -> u64;
+    /// Tokens this curve can sell in total
+    fn token_allocation(&self) -> u64;
+
+    /// Tokens in every step but the last, which also takes the remainder
+    fn step_tokens(&self) -> u64 {
+        self.token_allocation() / CURVE_STEPS
+    }
+
+    /// Step containing the token at `position`
+    fn step_of(&self, position: u64) -> u64 {
+        (position / self.step_tokens()).min(CURVE_STEPS - 1)
+    }
+
+    /// First token position of `step`
+    fn step_start(&self, step: u64) -> u64 {
+        step * self.step_tokens()
+    }
+
+    /// Token position just past the end of `step`
+    fn step_end(&self, step: u64) -> u64 {
+        if step == CURVE_STEPS - 1 {
+            self.token_allocation()
+        } else {
+            (step + 1) * self.step_tokens()
+        }
+    }
+
+    /// Prices of steps `0..=last`, in one pass
+    fn step_prices(&self, last: u64) -> Result<Vec<u64>> {
+        let mut prices = Vec::with_capacity(last as usize + 1);
+        prices.push(self.step_price(0)?);
+        for step in 0..last {
+            prices.push(self.next_step_price(step, prices[step as usize])?);
+        }
+        Ok(prices)
+    }
+}
+
+impl<T: StepSchedule> CurvePricing for T {
+    fn tokens_out(&self, sol_in: u64) -> Result<u64> {
+        require!(sol_in > 0, PumpFunError::InvalidAmount);
+
+        let mut budget = (sol_in as u128) * PRICE_DENOMINATOR;
+        let mut sold = self.tokens_sold();
+        let mut tokens_out = 0u64;
+        let mut step = self.step_of(sold);
+        let mut price = self.step_price(step)?;
+
+        while sold < self.token_allocation() {
+            let room = self.step_end(step) - sold;
+            let cost = price as u128 * (room as u128);
+
+            if budget < cost {
+                // Partial step: round tokens down
+                tokens_out += div_floor(budget, price as u128)? as u64;
+                return Ok(tokens_out);
+            }
+
+            budget -= cost;
+            tokens_out += room;
+            sold += room;
+            if sold < self.token_allocation() {
+                price = self.next_step_price(step, price)?;
+                step += 1;
+            }
+        }
+
+        // Allocation exhausted with at least a lamport left unspent
+        require!(budget < PRICE_DENOMINATOR, PumpFunError::InsufficientLiquidity);
+        Ok(tokens_out)
+    }
+
+    fn sol_in(&self, tokens_out: u64) -> Result<u64> {
+        require!(tokens_out > 0, PumpFunError::InvalidAmount);
+
+        let mut sold = self.tokens_sold();
+        require!(
+            tokens_out <= self.token_allocation().saturating_sub(sold),
+            PumpFunError::InsufficientLiquidity
+        );
+
+        let mut remaining = tokens_out;
+        let mut cost = 0u128;
+        let mut step = self.step_of(sold);
+        let mut price = self.step_price(step)?;
+        loop {
+            let room = (self.step_end(step) - sold).min(remaining);
+
+            cost += price as u128 * (room as u128);
+            sold += room;
+            remaining -= room;
+            if remaining == 0 {
+                break;
+            }
+            price = self.next_step_price(step, price)?;
+            step += 1;
+        }
+
+        // Round the SOL owed up
+        to_u64(div_ceil(cost, PRICE_DENOMINATOR)?)
+    }
+
+    fn sol_out(&self, tokens_in: u64) -> Result<u64> {
+        require!(tokens_in > 0, PumpFunError::InvalidAmount);
+
+        let sold = self.tokens_sold();
+        require!(tokens_in <= sold, PumpFunError::TokensSoldUnderflow);
+
+        // Value the tokens being returned from the lowest one up
+        let mut position = sold - tokens_in;
+        let mut value = 0u128;
+        let mut step = self.step_of(position);
+        let mut price = self.step_price(step)?;
+        loop {
+            let room = self.step_end(step).min(sold) - position;
+
+            value += price as u128 * (room as u128);
+            position += room;
+            if position == sold {
+                break;
+            }
+            price = self.next_step_price(step, price)?;
+            step += 1;
+        }
+
+        // Round the SOL paid out down
+        to_u64(div_floor(value, PRICE_DENOMINATOR)?)
+    }
+
+    fn tokens_in(&self, sol_out: u64) -> Result<u64> {
+        require!(sol_out > 0, PumpFunError::InvalidAmount);
+
+        let target = (sol_out as u128) * PRICE_DENOMINATOR;
+        let mut sold = self.tokens_sold();
+        require!(sold > 0, PumpFunError::InsufficientLiquidity);
+        let mut value = 0u128;
+        let mut tokens_in = 0u64;
+
+        // The walk goes down from the spot price, so price every step below it first
+        let prices = self.step_prices(self.step_of(sold - 1))?;
+        while sold > 0 {
+            let step = self.step_of(sold - 1);
+            let price = prices[step as usize] as u128;
+            let room = sold - self.step_start(step);
+            let step_value = price * (room as u128);
+
+            if value + step_value >= target {
+                // Partial step: round tokens up
+                tokens_in += div_ceil(target - value, price)? as u64;
+                return Ok(tokens_in);
+            }
+
+            value += step_value;
+            tokens_in += room;
+            sold -= room;
+        }
+
+        err!(PumpFunError::InsufficientLiquidity)
+    }
+
+    fn spot_price(&self) -> Result<u64> {
+        self.step_price(self.step_of(self.tokens_sold()))
+    }
+
+    fn tokens_to_price(&self, price: u64) -> Result<Option<u64>> {
+        // The spot price only changes at step boundaries
+        let sold = self.tokens_sold();
+        let mut step = self.step_of(sold);
+        let mut step_price = self.step_price(step)?;
+        loop {
+            if step_price >= price {
+                return Ok(Some(self.step_start(step).max(sold) - sold));
+            }
+            if step == CURVE_STEPS - 1 {
+                return Ok(None);
+            }
+            step_price = self.next_step_price(step, step_price)?;
+            step += 1;
+        }
+    }
+}
+
+/// Linear price schedule: start_price + (end_price - start_price) * step / (CURVE_STEPS - 1)
+pub struct Linear {
+    pub start_price: u64,
+    pub end_price: u64,
+    pub tokens_sold: u64,
+    pub token_allocation: u64,
+}
+
+impl StepSchedule for Linear {
+    fn step_price(&self, step: u64) -> Result<u64> {
+        let range = self.end_price
+            .checked_sub(self.start_price)
+            .ok_or(PumpFunError::InvalidCurveParams)?;
+        let increase = div_floor(
+            range as u128 * step as u128,
+            (CURVE_STEPS - 1) as u128,
+        )?;
+
+        to_u64(self.start_price as u128 + increase)
+    }
+
+    fn next_step_price(&self, step: u64, _price: u64) -> Result<u64> {
+        // Each price is a single division from the start price
+        self.step_price(step + 1)
+    }
+
+    fn tokens_sold(&self) -> u64 {
+        self.tokens_sold
+    }
+
+    fn token_allocation(&self) -> u64 {
+        self.token_allocation
+    }
+}
+
+/// Exponential price schedule: each step costs `growth_bps` more than the last
+pub struct Exponential {
+    pub start_price: u64,
+    pub growth_bps: u16,
+    pub tokens_sold: u64,
+    pub token_allocation: u64,
+}
+
+impl StepSchedule for Exponential {
+    fn step_price(&self, step: u64) -> Result<u64> {
+        exponential_step_price(self.start_price, self.growth_bps, step)
+    }
+
+    fn next_step_price(&self, _step: u64, price: u64) -> Result<u64> {
+        exponential_next_price(price, self.growth_bps)
+    }
+
+    fn tokens_sold(&self) -> u64 {
+        self.tokens_sold
+    }
+
+    fn token_allocation(&self) -> u64 {
+        self.token_allocation
+    }
+}
+
+/// Price of `step` on an exponential schedule
+/// Formula: p(0) = start_price, p(i + 1) = p(i) + floor(p(i) * growth_bps / 10000)
+///
+/// O(step); walkers call it once and then `exponential_next_price`.
+pub fn exponential_step_price(start_price: u64, growth_bps: u16, step: u64) -> Result<u64> {
+    let mut price = start_price;
+    for _ in 0..step {
+        price = exponential_next_price(price, growth_bps)?;
+    }
+    Ok(price)
+}
+
+/// One step of the exponential schedule: p + floor(p * growth_bps / 10000)
+pub fn exponential_next_price(price: u64, growth_bps: u16) -> Result<u64> {
+    // Narrow every step so price * growth_bps always fits in u128
+    to_u64(price as u128 + div_floor(price as u128 * growth_bps as u128, 10000)?)
+}
@@ -23,7 +23,7 @@ pub enum PumpFunError {
     #[msg("Slippage tolerance exceeded")]
     SlippageExceeded,
 
-    #[msg("Invalid token metadata")]
+    #[msg("Invalid token metadata: empty or contains control characters")]
     InvalidMetadata,
 
     #[msg("Unauthorized: invalid authority")]
@@ -43,4 +43,97 @@ pub enum PumpFunError {
 
     #[msg("Token account is not empty")]
     TokenAccountNotEmpty,
+
+    #[msg("Protocol fee exceeds the maximum allowed basis points")]
+    InvalidProtocolFee,
+
+    #[msg("Creation fee exceeds the maximum allowed amount")]
+    InvalidCreationFee,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Trading is halted on this bonding curve")]
+    TradingHalted,
+
+    #[msg("Curve vault balance does not cover the real SOL reserve")]
+    VaultInsolvent,
+
+    #[msg("Token name exceeds the maximum length")]
+    NameTooLong,
+
+    #[msg("Token symbol exceeds the maximum length")]
+    SymbolTooLong,
+
+    #[msg("Metadata URI exceeds the maximum length")]
+    UriTooLong,
+
+    #[msg("Metadata URI scheme is not allowed")]
+    UnsupportedUriScheme,
+
+    #[msg("Metadata length limit out of bounds")]
+    InvalidMetadataLimit,
+
+    #[msg("Tokens sold would exceed the curve's token allocation")]
+    CurveSupplyExceeded,
+
+    #[msg("Tokens returned exceed the tokens sold by the curve")]
+    TokensSoldUnderflow,
+
+    #[msg("Curve kind is not enabled by the protocol authority")]
+    CurveKindDisabled,
+
+    #[msg("Curve parameters are outside the configured bounds")]
+    InvalidCurveParams,
+
+    #[msg("Curve bounds are invalid")]
+    InvalidCurveBounds,
+
+    #[msg("Trade would overshoot the curve's graduation threshold")]
+    GraduationOvershoot,
+
+    #[msg("Liquidity has already migrated from this bonding curve")]
+    AlreadyMigrated,
+
+    #[msg("Pool fee exceeds the maximum allowed basis points")]
+    InvalidPoolFee,
+
+    #[msg("Insufficient liquidity in pool")]
+    InsufficientPoolLiquidity,
+
+    #[msg("Pool balance does not cover its SOL reserve")]
+    PoolInsolvent,
+
+    #[msg("LP lock duration is below the configured minimum")]
+    InvalidLpLock,
+
+    #[msg("LP tokens are still locked")]
+    LiquidityStillLocked,
+
+    #[msg("LP tokens have already been unlocked")]
+    AlreadyUnlocked,
+
+    #[msg("Migration target is invalid")]
+    InvalidMigrationTarget,
+
+    #[msg("Accounts do not match the configured migration target")]
+    InvalidMigrationAccounts,
+
+    #[msg("Migration venue did not take the curve's full liquidity")]
+    MigrationIncomplete,
+
+    #[msg("Migration fee or completer reward is outside the allowed bounds")]
+    InvalidMigrationFee,
+
+    #[msg("Liquidity has not migrated from this bonding curve yet")]
+    NotMigrated,
+
+    #[msg("Bonding curve still holds a real SOL reserve")]
+    CurveNotEmpty,
+
+    #[msg("Global config is not in the legacy layout")]
+    ConfigAlreadyMigrated,
 }
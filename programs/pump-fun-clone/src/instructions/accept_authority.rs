@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Accept a pending protocol authority transfer
+///
+/// Second step of a two-step authority transfer. Must be signed by the key
+/// stored in `pending_authority`, which then becomes the protocol authority.
+///
+/// Accounts:
+/// - new_authority: Proposed authority (signer)
+/// - global_config: Global configuration account (PDA)
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        constraint = global_config.pending_authority.is_some() @ PumpFunError::NoPendingAuthority,
+        constraint = global_config.pending_authority == Some(new_authority.key()) @ PumpFunError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    AcceptAuthority::execute(ctx)
+}
+
+impl<'info> AcceptAuthority<'info> {
+    fn execute(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        let clock = Clock::get()?;
+
+        let previous_authority = global_config.authority;
+        global_config.authority = ctx.accounts.new_authority.key();
+        global_config.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            previous_authority,
+            new_authority: global_config.authority,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
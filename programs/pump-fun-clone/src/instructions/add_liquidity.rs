@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Deposit SOL and tokens into a graduated curve's pool for LP tokens
+///
+/// Deposits keep the pool's reserve ratio. The provider chooses the SOL
+/// side; the token side and the LP minted follow from the reserves:
+///
+/// tokens_in = ceil(sol_amount * token_reserve / sol_reserve)
+/// lp_out = floor(sol_amount * lp_supply / sol_reserve)
+///
+/// Fails with `SlippageExceeded` if `tokens_in` exceeds `max_tokens_in` or
+/// `lp_out` is below `min_lp_out`.
+///
+/// Accounts:
+/// - provider: Liquidity provider (signer)
+/// - pool: Graduation pool state account (holds the SOL reserve)
+/// - mint: Token mint account
+/// - lp_mint: Pool LP mint
+/// - provider_token_account: Provider's token account
+/// - provider_lp_account: Provider's LP token account
+/// - pool_token_account: Pool's token account (holds the token reserve)
+/// - global_config: Global protocol configuration
+/// - token_program: Token-2022 program
+/// - system_program: System program
+#[derive(Accounts)]
+pub struct Liquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump,
+        has_one = mint @ PumpFunError::InvalidTokenMint,
+        has_one = lp_mint @ PumpFunError::InvalidTokenMint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = provider,
+        associated_token::token_program = token_program,
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = provider,
+        associated_token::token_program = token_program,
+    )]
+    pub provider_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<Liquidity>,
+    sol_amount: u64,
+    max_tokens_in: u64,
+    min_lp_out: u64,
+) -> Result<()> {
+    Liquidity::execute_add(ctx, sol_amount, max_tokens_in, min_lp_out)
+}
+
+impl<'info> Liquidity<'info> {
+    fn execute_add(
+        ctx: Context<Liquidity>,
+        sol_amount: u64,
+        max_tokens_in: u64,
+        min_lp_out: u64,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+
+        let (tokens_in, lp_out) = accounts.pool.quote_deposit(sol_amount)?;
+
+        // Slippage checks
+        require!(
+            tokens_in <= max_tokens_in && lp_out >= min_lp_out,
+            PumpFunError::SlippageExceeded
+        );
+
+        // SOL to the pool account through the System Program
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accounts.provider.to_account_info(),
+                    to: accounts.pool.to_account_info(),
+                },
+            ),
+            sol_amount,
+        )?;
+
+        // Tokens to the pool's token account
+        let cpi_accounts = token_2022::TransferChecked {
+            from: accounts.provider_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.pool_token_account.to_account_info(),
+            authority: accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, tokens_in, accounts.mint.decimals)?;
+
+        // Mint LP tokens to the provider
+        let mint_key = accounts.pool.mint;
+        let seeds = &[
+            b"pool",
+            mint_key.as_ref(),
+            &[accounts.pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: accounts.lp_mint.to_account_info(),
+            to: accounts.provider_lp_account.to_account_info(),
+            authority: accounts.pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022::mint_to(cpi_ctx, lp_out)?;
+
+        // Update pool state
+        let pool = &mut accounts.pool;
+        pool.update_after_deposit(sol_amount, tokens_in, lp_out)?;
+        pool.assert_solvent(&pool.to_account_info())?;
+
+        emit!(LiquidityAdded {
+            mint: pool.mint,
+            provider: accounts.provider.key(),
+            sol_in: sol_amount,
+            tokens_in,
+            lp_out,
+            sol_reserve: pool.sol_reserve,
+            token_reserve: pool.token_reserve,
+            lp_supply: pool.lp_supply,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub sol_in: u64,
+    pub tokens_in: u64,
+    /// LP tokens minted to the provider
+    pub lp_out: u64,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    pub lp_supply: u64,
+    pub timestamp: i64,
+}
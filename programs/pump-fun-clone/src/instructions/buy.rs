@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{Token2022, Mint, TokenAccount};
+use anchor_lang::system_program;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
 
 use crate::state::*;
 use crate::errors::PumpFunError;
 use crate::constants::*;
+use super::quote_buy::BuyQuote;
 
 /// Buy tokens from the bonding curve
 /// 
@@ -14,13 +17,21 @@ use crate::constants::*;
 /// - k = constant product
 /// 
 /// When buying: SOL in → tokens out
-/// New reserves: (x + sol_in) * (y - tokens_out) = k
+/// The protocol fee is taken from `sol_in` first; only the remainder
+/// (`sol_to_curve`) is priced on the curve and deposited in the vault.
+/// New reserves: (x + sol_to_curve) * (y - tokens_out) = k
+/// 
+/// The final buy is filled only up to graduation; the unused SOL is never
+/// debited and is reported as `sol_refunded`, and `min_tokens_out` is scaled
+/// to the filled share of `sol_in`.
 /// 
 /// Accounts:
 /// - buyer: Token buyer (signer, pays SOL)
 /// - bonding_curve: Bonding curve state account
 /// - mint: Token mint account
 /// - buyer_token_account: Buyer's token account (receives tokens)
+/// - curve_token_account: Bonding curve's token account (sends tokens)
+/// - curve_vault: Per-curve SOL vault (receives SOL after fees)
 /// - global_config: Global protocol configuration
 /// - treasury: Treasury account (receives protocol fees)
 /// - token_program: Token-2022 program
@@ -35,26 +46,44 @@ pub struct Buy<'info> {
         mut,
         seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
         bump = bonding_curve.bump,
-        constraint = !bonding_curve.completed @ PumpFunError::AlreadyCompleted
+        constraint = !bonding_curve.completed @ PumpFunError::AlreadyCompleted,
+        constraint = !bonding_curve.trading_halted @ PumpFunError::TradingHalted
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
     #[account(
-        mut,
         constraint = mint.key() == bonding_curve.mint @ PumpFunError::InvalidTokenMint
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::owner = buyer,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
+        mut,
+        seeds = [b"curve_vault", bonding_curve.mint.as_ref()],
+        bump = curve_vault.bump
+    )]
+    pub curve_vault: Account<'info, CurveVault>,
+
+    #[account(
+        mut,
         seeds = [b"global_config"],
-        bump
+        bump,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
@@ -71,7 +100,7 @@ pub struct Buy<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<Buy>,
     sol_in: u64,
     min_tokens_out: u64,
@@ -85,72 +114,105 @@ impl<'info> Buy<'info> {
         sol_in: u64,
         min_tokens_out: u64,
     ) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let buyer = &ctx.accounts.buyer;
-        let clock = Clock::get()?;
+        let fee_bps = ctx.accounts.global_config.protocol_fee_bps;
 
         // Validate input
         require!(sol_in >= MIN_SOL_AMOUNT, PumpFunError::MinSolAmountNotMet);
         require!(sol_in > 0, PumpFunError::InvalidAmount);
 
-        // Calculate tokens out using bonding curve formula
-        let tokens_out = calculate_tokens_out(
-            sol_in,
-            bonding_curve.virtual_sol_reserve,
-            bonding_curve.virtual_token_reserve,
-        )?;
+        // Same pricing as `quote_buy`: fee on the SOL side, rest priced on the curve
+        let quote = BuyQuote::new(&ctx.accounts.bonding_curve, fee_bps, sol_in)?;
 
-        // Slippage check
+        // Slippage check, pro rata if the buy was capped at graduation
         require!(
-            tokens_out >= min_tokens_out,
+            quote.tokens_out >= quote.min_tokens_for_fill(min_tokens_out)?,
             PumpFunError::SlippageExceeded
         );
 
-        // Calculate protocol fee
-        let protocol_fee = (sol_in as u128)
-            .checked_mul(PROTOCOL_FEE_BPS as u128)
-            .ok_or(PumpFunError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(PumpFunError::MathOverflow)? as u64;
-
-        let sol_after_fee = sol_in
-            .checked_sub(protocol_fee)
-            .ok_or(PumpFunError::MathOverflow)?;
-
-        // Transfer SOL from buyer to bonding curve (virtual reserve update)
-        // In reality, SOL goes to treasury/accumulates for LP
-        **buyer.to_account_info().try_borrow_mut_lamports()? -= sol_in;
-        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += sol_after_fee;
-        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += protocol_fee;
-
-        // Mint tokens to buyer
-        // Note: The bonding_curve PDA should be set as the mint authority
-        // This allows the program to mint tokens when users buy
+        // A final buy may be capped at graduation: only the filled `sol_in` is
+        // transferred, so the unused `sol_refunded` stays with the buyer
+        ctx.accounts.settle(
+            quote.sol_in,
+            quote.sol_refunded,
+            quote.protocol_fee,
+            quote.sol_to_curve,
+            quote.tokens_out,
+        )
+    }
+
+    /// Move funds for a priced buy and update curve state
+    ///
+    /// Shared by `buy` and `buy_exact_tokens` once the amounts are known:
+    /// `sol_to_curve` goes to the curve vault, `protocol_fee` to the treasury
+    /// and `tokens_out` from the curve's token account to the buyer.
+    /// `sol_refunded` is only reported in `TokenBought`.
+    pub(crate) fn settle(
+        &mut self,
+        sol_in: u64,
+        sol_refunded: u64,
+        protocol_fee: u64,
+        sol_to_curve: u64,
+        tokens_out: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // Transfer SOL from buyer: reserve to the curve vault, fee to treasury
+        // The buyer is system-owned, so debits must go through the System Program
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.buyer.to_account_info(),
+                    to: self.curve_vault.to_account_info(),
+                },
+            ),
+            sol_to_curve,
+        )?;
+
+        if protocol_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.buyer.to_account_info(),
+                        to: self.treasury.to_account_info(),
+                    },
+                ),
+                protocol_fee,
+            )?;
+        }
+
+        // Transfer tokens from the curve's pre-minted supply to the buyer
         let seeds = &[
             b"bonding_curve",
-            bonding_curve.mint.as_ref(),
-            &[bonding_curve.bump],
+            self.bonding_curve.mint.as_ref(),
+            &[self.bonding_curve.bump],
         ];
         let signer = &[&seeds[..]];
 
-        let cpi_accounts = anchor_spl::token_2022::MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.bonding_curve.to_account_info(),
+        let cpi_accounts = anchor_spl::token_2022::TransferChecked {
+            from: self.curve_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.buyer_token_account.to_account_info(),
+            authority: self.bonding_curve.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_program = self.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(
             cpi_program,
             cpi_accounts,
             signer,
         );
-        anchor_spl::token_2022::mint_to(cpi_ctx, tokens_out)?;
+        anchor_spl::token_2022::transfer_checked(cpi_ctx, tokens_out, self.mint.decimals)?;
 
         // Update bonding curve state
-        bonding_curve.update_after_buy(sol_in, tokens_out);
+        let bonding_curve = &mut self.bonding_curve;
+        bonding_curve.update_after_buy(sol_to_curve, tokens_out)?;
+        bonding_curve.record_fee(protocol_fee)?;
+        self.global_config.record_fee(protocol_fee)?;
+        bonding_curve.check_invariants(&self.curve_vault.to_account_info())?;
 
         // Check if curve is complete
-        let is_complete = is_complete(bonding_curve.virtual_sol_reserve);
+        let is_complete = bonding_curve.graduation_reached()?;
         if is_complete {
             bonding_curve.complete(&clock);
         }
@@ -158,8 +220,11 @@ impl<'info> Buy<'info> {
         // Emit buy event
         emit!(TokenBought {
             mint: bonding_curve.mint,
-            buyer: buyer.key(),
+            buyer: self.buyer.key(),
             sol_in,
+            sol_refunded,
+            protocol_fee,
+            sol_to_curve,
             tokens_out,
             virtual_sol_reserve: bonding_curve.virtual_sol_reserve,
             virtual_token_reserve: bonding_curve.virtual_token_reserve,
@@ -175,7 +240,14 @@ impl<'info> Buy<'info> {
 pub struct TokenBought {
     pub mint: Pubkey,
     pub buyer: Pubkey,
+    /// Total SOL paid by the buyer (`protocol_fee + sol_to_curve`)
     pub sol_in: u64,
+    /// Requested SOL not charged because the buy was capped at graduation
+    pub sol_refunded: u64,
+    /// SOL sent to the treasury
+    pub protocol_fee: u64,
+    /// SOL deposited in the curve vault and priced on the curve
+    pub sol_to_curve: u64,
     pub tokens_out: u64,
     pub virtual_sol_reserve: u64,
     pub virtual_token_reserve: u64,
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::PumpFunError;
+use crate::constants::*;
+use super::buy::Buy;
+
+/// Buy an exact amount of tokens from the bonding curve
+///
+/// Exact-output counterpart of `buy`, using the same accounts. The SOL that
+/// must reach the curve comes from the curve kind's `sol_in` (for constant
+/// product, `calculate_sol_in`) and is grossed up for the protocol fee, both
+/// rounded up so the curve never gives out more than it is paid for.
+///
+/// Constant product: sol_to_curve = ceil(k / (y - tokens_out)) - x
+/// sol_in = ceil(sol_to_curve * 10000 / (10000 - fee_bps))
+///
+/// The trade fails with `SlippageExceeded` if `sol_in` exceeds `max_sol_in`.
+pub(crate) fn handler(
+    ctx: Context<Buy>,
+    tokens_out: u64,
+    max_sol_in: u64,
+) -> Result<()> {
+    Buy::execute_exact_tokens(ctx, tokens_out, max_sol_in)
+}
+
+impl<'info> Buy<'info> {
+    fn execute_exact_tokens(
+        ctx: Context<Buy>,
+        tokens_out: u64,
+        max_sol_in: u64,
+    ) -> Result<()> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let fee_bps = ctx.accounts.global_config.protocol_fee_bps;
+
+        // Validate input
+        require!(tokens_out > 0, PumpFunError::InvalidAmount);
+
+        // SOL the curve needs for exactly `tokens_out`; fails with
+        // `InsufficientLiquidity` if the reserve cannot cover it
+        let sol_needed = bonding_curve.pricing().sol_in(tokens_out)?;
+
+        // An exact-output buy cannot be capped, so it must not pass graduation
+        if let Some((_, cap_tokens)) = bonding_curve.graduation_cap()? {
+            require!(tokens_out <= cap_tokens, PumpFunError::GraduationOvershoot);
+        }
+
+        // Gross up for the fee, then split exactly as `buy` does
+        let sol_in = calculate_gross_for_net(sol_needed, fee_bps)?;
+        let (protocol_fee, sol_to_curve) = split_fee(sol_in, fee_bps)?;
+
+        require!(sol_in >= MIN_SOL_AMOUNT, PumpFunError::MinSolAmountNotMet);
+
+        // Slippage check
+        require!(
+            sol_in <= max_sol_in,
+            PumpFunError::SlippageExceeded
+        );
+
+        ctx.accounts.settle(sol_in, 0, protocol_fee, sol_to_curve, tokens_out)
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Cancel a pending protocol authority transfer
+///
+/// Clears `pending_authority` so the proposed key can no longer accept.
+///
+/// Accounts:
+/// - authority: Current protocol authority (signer)
+/// - global_config: Global configuration account (PDA)
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ PumpFunError::Unauthorized,
+        constraint = global_config.pending_authority.is_some() @ PumpFunError::NoPendingAuthority
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    CancelAuthorityTransfer::execute(ctx)
+}
+
+impl<'info> CancelAuthorityTransfer<'info> {
+    fn execute(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        let clock = Clock::get()?;
+
+        let cancelled_authority = global_config
+            .pending_authority
+            .take()
+            .ok_or(PumpFunError::NoPendingAuthority)?;
+
+        emit!(AuthorityTransferCancelled {
+            authority: global_config.authority,
+            cancelled_authority,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::*;
+use crate::curve::CurveKind;
+use crate::errors::PumpFunError;
+
+/// Close a migrated bonding curve and return its rent to the creator
+///
+/// Once `complete` or `withdraw_for_migration` has moved the liquidity out,
+/// `real_sol_reserve` is zero and the bonding curve, its vault and its token
+/// account only hold rent. This archives the curve's final state in a
+/// `CurveClosed` event, then closes all three accounts, sending their
+/// lamports to the creator who paid for them at `create`. The creator or the
+/// protocol authority may call it. A curve still holding a real SOL reserve
+/// fails with `CurveNotEmpty`.
+///
+/// Stray deposits do not block the close. Tokens sent to the curve's token
+/// account after migration are burned first. Lamports sent to the vault
+/// above its rent-exempt minimum are forfeited to the creator along with the
+/// rent. The mint, pool and LP lock are left untouched.
+///
+/// Accounts:
+/// - closer: Creator or protocol authority (signer)
+/// - creator: Curve creator (receives the rent)
+/// - bonding_curve: Bonding curve state account (closed)
+/// - mint: Token mint account
+/// - curve_token_account: Bonding curve's token account (closed)
+/// - curve_vault: Per-curve SOL vault (closed)
+/// - global_config: Global protocol configuration
+/// - token_program: Token-2022 program
+#[derive(Accounts)]
+pub struct CloseCurve<'info> {
+    pub closer: Signer<'info>,
+
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        has_one = creator @ PumpFunError::Unauthorized,
+        constraint = bonding_curve.migrated @ PumpFunError::NotMigrated,
+        close = creator
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == bonding_curve.mint @ PumpFunError::InvalidTokenMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_vault", bonding_curve.mint.as_ref()],
+        bump = curve_vault.bump,
+        close = creator
+    )]
+    pub curve_vault: Account<'info, CurveVault>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        constraint = closer.key() == bonding_curve.creator
+            || closer.key() == global_config.authority @ PumpFunError::Unauthorized,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseCurve>) -> Result<()> {
+    CloseCurve::execute(ctx)
+}
+
+impl<'info> CloseCurve<'info> {
+    fn execute(ctx: Context<CloseCurve>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+
+        // Migration leaves nothing owed to traders in the vault
+        require!(
+            accounts.bonding_curve.real_sol_reserve == 0,
+            PumpFunError::CurveNotEmpty
+        );
+
+        let rent_reclaimed = accounts.bonding_curve.to_account_info().lamports()
+            + accounts.curve_vault.to_account_info().lamports()
+            + accounts.curve_token_account.to_account_info().lamports();
+
+        let bonding_curve = &accounts.bonding_curve;
+        let mint_key = bonding_curve.mint;
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bonding_curve.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Burn tokens donated after migration so the account can be closed
+        let donated_tokens = accounts.curve_token_account.amount;
+        if donated_tokens > 0 {
+            let cpi_accounts = token_2022::Burn {
+                mint: accounts.mint.to_account_info(),
+                from: accounts.curve_token_account.to_account_info(),
+                authority: accounts.bonding_curve.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            token_2022::burn(cpi_ctx, donated_tokens)?;
+        }
+
+        let cpi_accounts = token_2022::CloseAccount {
+            account: accounts.curve_token_account.to_account_info(),
+            destination: accounts.creator.to_account_info(),
+            authority: accounts.bonding_curve.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022::close_account(cpi_ctx)?;
+
+        let bonding_curve = &accounts.bonding_curve;
+        emit!(CurveClosed {
+            mint: mint_key,
+            creator: bonding_curve.creator,
+            curve_kind: bonding_curve.curve_kind,
+            graduation_threshold: bonding_curve.graduation_threshold,
+            tokens_sold: bonding_curve.tokens_sold,
+            fees_collected: bonding_curve.fees_collected,
+            migrated_sol: bonding_curve.migrated_sol,
+            migrated_tokens: bonding_curve.migrated_tokens,
+            created_at: bonding_curve.created_at,
+            completed_at: bonding_curve.completed_at,
+            rent_reclaimed,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// Final state of a closed bonding curve, kept for indexers
+#[event]
+pub struct CurveClosed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub curve_kind: CurveKind,
+    pub graduation_threshold: u64,
+    pub tokens_sold: u64,
+    /// Trading fees the curve paid to the treasury
+    pub fees_collected: u64,
+    /// SOL that reached the migration target
+    pub migrated_sol: u64,
+    /// Liquidity tokens that reached the migration target
+    pub migrated_tokens: u64,
+    pub created_at: i64,
+    pub completed_at: Option<i64>,
+    /// Lamports returned to the creator from the three closed accounts
+    pub rent_reclaimed: u64,
+    pub timestamp: i64,
+}
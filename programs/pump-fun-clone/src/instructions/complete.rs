@@ -1,74 +1,203 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 use crate::state::*;
 use crate::errors::PumpFunError;
 use crate::constants::*;
+use crate::migration::Migration;
 
-/// Complete/graduate the bonding curve to a DEX liquidity pool
-/// 
-/// This instruction can be called by anyone once the bonding curve reaches
-/// the completion threshold. It marks the curve as complete and prepares
-/// for DEX pool creation.
-/// 
-/// In a full implementation, this would:
-/// 1. Create a Raydium/PumpSwap liquidity pool
-/// 2. Add liquidity using accumulated SOL + reserved tokens
-/// 3. Burn or lock LP tokens
-/// 4. Disable further trading on the bonding curve
-/// 
-/// For now, this is a stub that marks completion and emits an event.
-/// 
+/// Complete/graduate the bonding curve into its migration venue
+///
+/// This instruction can be called by anyone once the bonding curve has met
+/// its graduation criterion, whether or not the last buy already marked it
+/// complete. It runs once per curve and:
+/// 1. Marks the curve complete, which stops buys and sells on the curve
+/// 2. Burns the allocated tokens the curve never sold
+/// 3. Takes `GlobalConfig.migration_fee` from the `real_sol_reserve`, paying
+///    `GlobalConfig.completer_reward` of it to the completer and the rest to
+///    the treasury
+/// 4. Moves the remaining SOL and the liquidity tokens
+///    (`TOTAL_SUPPLY - token_allocation`) into `GlobalConfig.migration_target`
+///    through its adapter (see `migration.rs`)
+/// 5. Locks the LP tokens received in the `LpLock` PDA
+///
+/// With the default `MigrationTarget::Pool`, the venue is a new `Pool` with
+/// swap fee `GlobalConfig.pool_fee_bps`, and the pool accounts below are
+/// required. An external venue instead takes the migration escrow plus its
+/// own accounts as remaining accounts, and the pool accounts must be omitted.
+///
+/// The locked LP tokens can only be claimed by the creator, after the
+/// `lp_unlock_delay` chosen at `create`; without one they stay locked
+/// forever. The completer pays the rent of the new accounts, which the
+/// completer reward is meant to cover.
+///
+/// The pool's token account is an ATA of the mint, so anyone can create it
+/// once the mint exists. It is created only if missing, so that creating it
+/// first cannot block graduation. Tokens sent to it before `complete` stay
+/// outside the pool's reserves.
+///
 /// Accounts:
-/// - completer: Anyone can call this (signer)
+/// - completer: Anyone can call this (signer, pays rent, receives the completer reward)
 /// - bonding_curve: Bonding curve state account
 /// - mint: Token mint account
+/// - curve_token_account: Bonding curve's token account (sends liquidity tokens)
+/// - curve_vault: Per-curve SOL vault (sends the real SOL reserve)
+/// - pool: Graduation pool state account (PDA, holds the SOL reserve; `Pool` target only)
+/// - lp_mint: Pool LP mint (PDA, mint authority is the pool; `Pool` target only)
+/// - pool_token_account: Pool's token account (ATA, holds the token reserve; `Pool` target only,
+///   may already exist)
+/// - lp_lock: Lock holding the initial LP supply (PDA)
+/// - lock_lp_account: Lock's LP token account (ATA; `Pool` target only)
+/// - migration_escrow: System-owned PDA passing the SOL reserve on (external targets only)
 /// - global_config: Global protocol configuration
+/// - treasury: Treasury account (receives the migration fee net of the reward)
+/// - token_program: Token-2022 program
+/// - associated_token_program: Associated Token program
+/// - system_program: System program
 #[derive(Accounts)]
 pub struct Complete<'info> {
+    #[account(mut)]
     pub completer: Signer<'info>,
 
     #[account(
         mut,
         seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
         bump = bonding_curve.bump,
-        constraint = !bonding_curve.completed @ PumpFunError::AlreadyCompleted,
-        constraint = is_complete(bonding_curve.virtual_sol_reserve) @ PumpFunError::NotCompleted
+        constraint = !bonding_curve.migrated @ PumpFunError::AlreadyMigrated,
+        constraint = !bonding_curve.trading_halted @ PumpFunError::TradingHalted
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
 
-    /// CHECK: Token mint validated by constraint
     #[account(
+        mut,
         constraint = mint.key() == bonding_curve.mint @ PumpFunError::InvalidTokenMint
     )]
-    pub mint: UncheckedAccount<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
-        seeds = [b"global_config"],
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_vault", bonding_curve.mint.as_ref()],
+        bump = curve_vault.bump
+    )]
+    pub curve_vault: Box<Account<'info, CurveVault>>,
+
+    #[account(
+        init,
+        payer = completer,
+        space = Pool::SIZE,
+        seeds = [b"pool", mint.key().as_ref()],
         bump
     )]
-    pub global_config: Account<'info, GlobalConfig>,
+    pub pool: Option<Box<Account<'info, Pool>>>,
+
+    #[account(
+        init,
+        payer = completer,
+        seeds = [b"lp_mint", mint.key().as_ref()],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    pub lp_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init_if_needed,
+        payer = completer,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init,
+        payer = completer,
+        space = LpLock::SIZE,
+        seeds = [b"lp_lock", mint.key().as_ref()],
+        bump
+    )]
+    pub lp_lock: Box<Account<'info, LpLock>>,
+
+    #[account(
+        init,
+        payer = completer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock,
+        associated_token::token_program = token_program,
+    )]
+    pub lock_lp_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"migration_escrow", mint.key().as_ref()],
+        bump
+    )]
+    pub migration_escrow: Option<SystemAccount<'info>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: Treasury PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [b"treasury", global_config.key().as_ref()],
+        bump = global_config.treasury_bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Complete>) -> Result<()> {
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Complete<'info>>) -> Result<()> {
     Complete::execute(ctx)
 }
 
 impl<'info> Complete<'info> {
-    fn execute(ctx: Context<Complete>) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
+    fn execute(ctx: Context<'_, '_, 'info, 'info, Complete<'info>>) -> Result<()> {
+        let accounts = ctx.accounts;
         let clock = Clock::get()?;
 
-        // Verify completion threshold is met
+        // A `Wallet` target migrates through `withdraw_for_migration` instead
+        let adapter = accounts.global_config.migration_target.adapter()?;
+
+        // Verify the curve has graduated; the final buy usually marks it already
         require!(
-            is_complete(bonding_curve.virtual_sol_reserve),
+            accounts.bonding_curve.completed || accounts.bonding_curve.graduation_reached()?,
             PumpFunError::NotCompleted
         );
+        if !accounts.bonding_curve.completed {
+            accounts.bonding_curve.complete(&clock);
+        }
 
-        // Mark as completed
-        bonding_curve.complete(&clock);
+        let bonding_curve = &accounts.bonding_curve;
+        let creator = bonding_curve.creator;
+        let lp_unlock_delay = bonding_curve.lp_unlock_delay;
+        let (migration_fee, completer_reward) = accounts
+            .global_config
+            .migration_fee_split(bonding_curve.real_sol_reserve);
+        let migration = Migration {
+            sol_amount: bonding_curve.real_sol_reserve - migration_fee,
+            token_amount: bonding_curve.liquidity_tokens()?,
+            tokens_burned: bonding_curve.unsold_tokens()?,
+        };
 
-        // Emit completion event
-        // In production, this would trigger DEX pool creation via CPI
         emit!(CurveCompleted {
             mint: bonding_curve.mint,
             creator: bonding_curve.creator,
@@ -77,14 +206,76 @@ impl<'info> Complete<'info> {
             real_sol_reserve: bonding_curve.real_sol_reserve,
             tokens_sold: bonding_curve.tokens_sold,
             completed_at: bonding_curve.completed_at.unwrap(),
+            completer: accounts.completer.key(),
+            migration_fee,
+            completer_reward,
             timestamp: clock.unix_timestamp,
         });
 
-        // TODO: In production, add CPI calls here to:
-        // 1. Create Raydium/PumpSwap pool
-        // 2. Add liquidity: real_sol_reserve SOL + RESERVED_TOKENS tokens
-        // 3. Burn or lock LP tokens
-        // 4. Transfer ownership/disable bonding curve
+        let mint_key = bonding_curve.mint;
+        let curve_seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bonding_curve.bump],
+        ];
+        let curve_signer = &[&curve_seeds[..]];
+
+        // Burn the unsold part of the allocation so only liquidity tokens remain
+        if migration.tokens_burned > 0 {
+            let cpi_accounts = token_2022::Burn {
+                mint: accounts.mint.to_account_info(),
+                from: accounts.curve_token_account.to_account_info(),
+                authority: accounts.bonding_curve.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                cpi_accounts,
+                curve_signer,
+            );
+            token_2022::burn(cpi_ctx, migration.tokens_burned)?;
+        }
+
+        // Move the liquidity into the configured venue
+        let receipt = adapter.migrate(
+            accounts,
+            &ctx.bumps,
+            ctx.remaining_accounts,
+            &migration,
+            &clock,
+        )?;
+
+        // Pay the migration fee out of the vault, which is program-owned.
+        // This comes after the adapter's CPIs, which may include the completer
+        **accounts.curve_vault.to_account_info().try_borrow_mut_lamports()? -= migration_fee;
+        **accounts.treasury.to_account_info().try_borrow_mut_lamports()? += migration_fee - completer_reward;
+        **accounts.completer.to_account_info().try_borrow_mut_lamports()? += completer_reward;
+
+        accounts.bonding_curve.record_migration(migration.sol_amount, migration.token_amount);
+        accounts.bonding_curve.assert_vault_solvent(&accounts.curve_vault.to_account_info())?;
+
+        let lp_lock = &mut accounts.lp_lock;
+        lp_lock.mint = mint_key;
+        lp_lock.pool = receipt.pool;
+        lp_lock.lp_mint = receipt.lp_mint;
+        lp_lock.beneficiary = creator;
+        lp_lock.amount = receipt.lp_amount;
+        lp_lock.locked_at = clock.unix_timestamp;
+        lp_lock.unlock_at = lp_unlock_delay
+            .map(|delay| clock.unix_timestamp.checked_add(delay).ok_or(PumpFunError::MathOverflow))
+            .transpose()?;
+        lp_lock.unlocked = false;
+        lp_lock.bump = ctx.bumps.lp_lock;
+
+        emit!(LiquidityLocked {
+            mint: mint_key,
+            pool: lp_lock.pool,
+            lp_lock: lp_lock.key(),
+            lp_mint: lp_lock.lp_mint,
+            beneficiary: lp_lock.beneficiary,
+            amount: lp_lock.amount,
+            unlock_at: lp_lock.unlock_at,
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
@@ -99,5 +290,62 @@ pub struct CurveCompleted {
     pub real_sol_reserve: u64,
     pub tokens_sold: u64,
     pub completed_at: i64,
+    /// Caller of `complete`
+    pub completer: Pubkey,
+    /// Taken from `real_sol_reserve` before migrating
+    pub migration_fee: u64,
+    /// Part of `migration_fee` paid to the completer, the rest went to the treasury
+    pub completer_reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    /// SOL moved from the curve vault
+    pub sol_reserve: u64,
+    /// Liquidity tokens moved from the curve's token account
+    pub token_reserve: u64,
+    /// Initial LP supply, minted into the LP lock
+    pub lp_supply: u64,
+    /// Unsold allocated tokens burned
+    pub tokens_burned: u64,
+    pub fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityMigrated {
+    pub mint: Pubkey,
+    /// External AMM program the liquidity moved into
+    pub program_id: Pubkey,
+    /// Venue pool holding the liquidity
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    /// SOL moved from the curve vault
+    pub sol_amount: u64,
+    /// Liquidity tokens moved from the curve's token account
+    pub token_amount: u64,
+    /// LP tokens the venue minted into the LP lock
+    pub lp_amount: u64,
+    /// Unsold allocated tokens burned
+    pub tokens_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityLocked {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_lock: Pubkey,
+    pub lp_mint: Pubkey,
+    /// Creator who may unlock the LP tokens
+    pub beneficiary: Pubkey,
+    /// LP tokens locked
+    pub amount: u64,
+    /// When the creator can unlock them, or `None` if the lock is permanent
+    pub unlock_at: Option<i64>,
     pub timestamp: i64,
 }
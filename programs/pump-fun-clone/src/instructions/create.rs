@@ -1,48 +1,60 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, AssociatedToken};
 use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
-use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensionsMut;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
 use anchor_spl::token_2022::spl_token_2022::state::Mint;
-use anchor_spl::token_2022::spl_token_metadata_interface::instruction::{
-    CreateMetadataAccountV3, CreateMetadataAccountV3InstructionArgs,
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_2022_extensions::{
+    metadata_pointer_initialize, token_metadata_initialize, MetadataPointerInitialize,
+    TokenMetadataInitialize,
 };
-use anchor_spl::token_2022::spl_token_metadata_interface::state::TokenMetadata;
-use anchor_spl::token_2022::{Token2022, TokenAccount, Mint as TokenMint};
-use anchor_spl::token::{self, Mint, TokenAccount as TokenAccountOld};
-use anchor_spl::associated_token::AssociatedToken;
 
 use crate::state::*;
+use crate::curve::CurveKind;
 use crate::errors::PumpFunError;
 use crate::constants::*;
 
 /// Creates a new token with Token-2022, metadata, and initializes bonding curve
 /// 
+/// The mint is allocated and initialized here with the MetadataPointer and
+/// TokenMetadata extensions, both pointing at the mint itself. The bonding
+/// curve PDA is the metadata update authority, and no freeze authority is set.
+/// 
+/// The full `TOTAL_SUPPLY` is minted once into the bonding curve's token
+/// account, after which the mint authority is revoked so supply is fixed.
+/// Buys transfer out of this account and sells transfer back into it.
+/// 
+/// `curve_kind` selects the pricing model and must be enabled and within the
+/// price bounds configured in `GlobalConfig`. `curve_params` sets the curve's
+/// initial virtual reserves, graduation threshold and token allocation, each
+/// within the ranges configured in `GlobalConfig`.
+/// 
+/// `lp_unlock_delay` is how long after graduation the pool's initial LP
+/// supply stays locked before the creator can claim it; it must be at least
+/// `GlobalConfig.min_lp_lock_duration`. `None` locks it permanently.
+/// 
 /// Accounts:
-/// - creator: Token creator (signer, pays creation fee)
-/// - mint: New token mint (Token-2022 with metadata extension)
-/// - metadata: Token metadata account
+/// - creator: Token creator (signer, pays creation fee and mint rent)
+/// - mint: New token mint keypair (signer, Token-2022 with metadata extensions)
 /// - bonding_curve: Bonding curve state account (PDA)
+/// - curve_vault: Per-curve SOL vault (PDA)
+/// - curve_token_account: Bonding curve's token account holding the supply (ATA)
 /// - global_config: Global protocol configuration
 /// - treasury: Treasury account (receives creation fee)
 /// - token_program: Token-2022 program
 /// - associated_token_program: Associated Token program
-/// - metadata_program: Token Metadata program
 /// - system_program: System program
-/// - rent: Rent sysvar
 #[derive(Accounts)]
 pub struct Create<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
-    /// Token mint account (Token-2022 with metadata pointer extension)
-    /// CHECK: Validated by Token-2022 program
+    /// Token mint account, allocated and initialized by this instruction
     #[account(mut)]
-    pub mint: UncheckedAccount<'info>,
-
-    /// Token metadata account
-    /// CHECK: Validated by metadata program
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
+    pub mint: Signer<'info>,
 
     /// Bonding curve state account
     #[account(
@@ -54,10 +66,26 @@ pub struct Create<'info> {
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
+    /// Per-curve SOL vault holding the real SOL reserve
+    #[account(
+        init,
+        payer = creator,
+        space = CurveVault::SIZE,
+        seeds = [b"curve_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub curve_vault: Account<'info, CurveVault>,
+
+    /// Bonding curve's associated token account, receives the full supply
+    /// CHECK: Created via CPI once the mint exists; address validated by the ATA program
+    #[account(mut)]
+    pub curve_token_account: UncheckedAccount<'info>,
+
     /// Global configuration account
     #[account(
         seeds = [b"global_config"],
-        bump
+        bump,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
@@ -72,20 +100,19 @@ pub struct Create<'info> {
 
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    /// CHECK: Token Metadata program
-    pub metadata_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
-    /// CHECK: Rent sysvar
-    pub rent: UncheckedAccount<'info>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<Create>,
     name: String,
     symbol: String,
     uri: String,
+    curve_kind: CurveKind,
+    curve_params: CurveParams,
+    lp_unlock_delay: Option<i64>,
 ) -> Result<()> {
-    Create::execute(ctx, name, symbol, uri)
+    Create::execute(ctx, name, symbol, uri, curve_kind, curve_params, lp_unlock_delay)
 }
 
 impl<'info> Create<'info> {
@@ -94,6 +121,9 @@ impl<'info> Create<'info> {
         name: String,
         symbol: String,
         uri: String,
+        curve_kind: CurveKind,
+        curve_params: CurveParams,
+        lp_unlock_delay: Option<i64>,
     ) -> Result<()> {
         let creator = &ctx.accounts.creator;
         let mint = &ctx.accounts.mint;
@@ -101,36 +131,228 @@ impl<'info> Create<'info> {
         let global_config = &ctx.accounts.global_config;
         let clock = Clock::get()?;
 
+        // Validate name, symbol and uri against configured limits
+        global_config.validate_metadata(&name, &symbol, &uri)?;
+
+        // Validate the pricing model against authority-approved bounds
+        global_config.validate_curve_kind(&curve_kind)?;
+        global_config.validate_curve_params(&curve_kind, &curve_params)?;
+        global_config.validate_lp_unlock_delay(lp_unlock_delay)?;
+
         // Verify creation fee payment
         require!(
             creator.lamports() >= global_config.creation_fee,
             PumpFunError::InsufficientCreationFee
         );
 
-        // Transfer creation fee to treasury
-        **ctx.accounts.creator.to_account_info().try_borrow_mut_lamports()? -= global_config.creation_fee;
-        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += global_config.creation_fee;
+        // Transfer creation fee to treasury via the System Program
+        if global_config.creation_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: creator.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                global_config.creation_fee,
+            )?;
+        }
 
         // Initialize bonding curve
-        let bump = ctx.bumps.get("bonding_curve").unwrap();
-        bonding_curve.initialize(mint.key(), creator.key(), *bump, &clock);
+        bonding_curve.initialize(
+            mint.key(),
+            creator.key(),
+            curve_kind,
+            &curve_params,
+            global_config.graduation_criterion,
+            lp_unlock_delay,
+            ctx.bumps.bonding_curve,
+            &clock,
+        );
+
+        // A curve must not start out graduated
+        require!(
+            !ctx.accounts.bonding_curve.graduation_reached()?,
+            PumpFunError::InvalidCurveParams
+        );
+
+        // Initialize the curve's SOL vault
+        let curve_vault = &mut ctx.accounts.curve_vault;
+        curve_vault.bonding_curve = ctx.accounts.bonding_curve.key();
+        curve_vault.bump = ctx.bumps.curve_vault;
+
+        // Allocate and initialize the Token-2022 mint with on-chain metadata
+        ctx.accounts.initialize_mint(
+            ctx.bumps.bonding_curve,
+            name.clone(),
+            symbol.clone(),
+            uri.clone(),
+        )?;
+
+        // Mint the fixed supply into the curve and revoke the mint authority
+        ctx.accounts.mint_fixed_supply(ctx.bumps.bonding_curve)?;
 
         // Emit create event
         emit!(TokenCreated {
-            mint: mint.key(),
-            creator: creator.key(),
-            name: name.clone(),
-            symbol: symbol.clone(),
+            mint: ctx.accounts.mint.key(),
+            creator: ctx.accounts.creator.key(),
+            name,
+            symbol,
+            uri,
+            curve_kind,
+            curve_params,
+            lp_unlock_delay,
             timestamp: clock.unix_timestamp,
         });
 
-        // Note: Token mint and metadata initialization should be done via CPI
-        // or in a separate instruction. For simplicity, we assume the mint
-        // is already initialized with Token-2022 and metadata pointer extension.
-        // In production, you would use CPI to:
-        // 1. Initialize mint with Token-2022
-        // 2. Set metadata pointer extension
-        // 3. Create metadata account with name, symbol, uri
+        Ok(())
+    }
+
+    /// Create the mint account and initialize it via Token-2022 CPIs:
+    /// 1. Allocate the mint with room for MetadataPointer, funded for metadata too
+    /// 2. Point the metadata at the mint itself
+    /// 3. Initialize the mint with the bonding curve as authority, no freeze authority
+    /// 4. Write name, symbol and uri into the TokenMetadata extension
+    fn initialize_mint(
+        &self,
+        bonding_curve_bump: u8,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let mint_key = self.mint.key();
+        let bonding_curve_key = self.bonding_curve.key();
+
+        // Base mint + MetadataPointer is allocated up front; Token-2022 reallocs
+        // for the variable-length TokenMetadata, so fund rent for both now
+        let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&[
+            ExtensionType::MetadataPointer,
+        ])?;
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(bonding_curve_key))?,
+            mint: mint_key,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: vec![],
+        };
+        let metadata_len = metadata.tlv_size_of()?;
+        let lamports = Rent::get()?.minimum_balance(mint_len + metadata_len);
+
+        system_program::create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: self.mint.to_account_info(),
+                },
+            ),
+            lamports,
+            mint_len as u64,
+            self.token_program.key,
+        )?;
+
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            Some(bonding_curve_key),
+            Some(mint_key),
+        )?;
+
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_2022::InitializeMint2 {
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            TOKEN_DECIMALS,
+            &bonding_curve_key,
+            None,
+        )?;
+
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bonding_curve_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    metadata: self.mint.to_account_info(),
+                    update_authority: self.bonding_curve.to_account_info(),
+                    mint_authority: self.bonding_curve.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+                signer,
+            ),
+            name,
+            symbol,
+            uri,
+        )?;
+
+        Ok(())
+    }
+
+    /// Create the bonding curve's token account, mint `TOTAL_SUPPLY` into it
+    /// and permanently revoke the mint authority
+    fn mint_fixed_supply(&self, bonding_curve_bump: u8) -> Result<()> {
+        let mint_key = self.mint.key();
+
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.creator.to_account_info(),
+                associated_token: self.curve_token_account.to_account_info(),
+                authority: self.bonding_curve.to_account_info(),
+                mint: self.mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bonding_curve_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.curve_token_account.to_account_info(),
+                    authority: self.bonding_curve.to_account_info(),
+                },
+                signer,
+            ),
+            TOTAL_SUPPLY,
+        )?;
+
+        token_2022::set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_2022::SetAuthority {
+                    current_authority: self.bonding_curve.to_account_info(),
+                    account_or_mint: self.mint.to_account_info(),
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
 
         Ok(())
     }
@@ -142,5 +364,9 @@ pub struct TokenCreated {
     pub creator: Pubkey,
     pub name: String,
     pub symbol: String,
+    pub uri: String,
+    pub curve_kind: CurveKind,
+    pub curve_params: CurveParams,
+    pub lp_unlock_delay: Option<i64>,
     pub timestamp: i64,
 }
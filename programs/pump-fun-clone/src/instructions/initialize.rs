@@ -1,8 +1,6 @@
 use anchor_lang::prelude::*;
 
 use crate::state::*;
-use crate::errors::PumpFunError;
-use crate::constants::*;
 
 /// Initialize the global configuration account
 /// 
@@ -39,7 +37,7 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Initialize>, authority: Pubkey) -> Result<()> {
+pub(crate) fn handler(ctx: Context<Initialize>, authority: Pubkey) -> Result<()> {
     let global_config = &mut ctx.accounts.global_config;
     let treasury_bump = ctx.bumps.get("treasury").copied().unwrap();
 
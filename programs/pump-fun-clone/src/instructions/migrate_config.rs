@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Upgrade a global config created by the original program
+///
+/// `GlobalConfig` has grown since the first deploy, so a config account
+/// written back then is too short to deserialize. This runs once, by the
+/// protocol authority recorded in the legacy account, and:
+/// 1. Tops the account up to the rent-exempt minimum for `GlobalConfig::SIZE`
+/// 2. Reallocates it to `GlobalConfig::SIZE`
+/// 3. Rewrites it with the legacy fields kept and every newer field set to
+///    the value `initialize` gives it (`DEFAULT_*` and friends)
+///
+/// An account that is already `GlobalConfig::SIZE` fails with
+/// `ConfigAlreadyMigrated`.
+///
+/// Accounts:
+/// - authority: Protocol authority in the legacy config (signer, pays rent)
+/// - global_config: Global configuration account (PDA, reallocated)
+/// - system_program: System program
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Too short to load as `GlobalConfig`; the handler checks its
+    /// size and discriminator before reading it as `LegacyGlobalConfig`
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    MigrateConfig::execute(ctx)
+}
+
+impl<'info> MigrateConfig<'info> {
+    fn execute(ctx: Context<MigrateConfig>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+        let config_info = accounts.global_config.to_account_info();
+
+        require!(
+            config_info.data_len() == LegacyGlobalConfig::SIZE,
+            PumpFunError::ConfigAlreadyMigrated
+        );
+        let legacy = {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data[..8] == GlobalConfig::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            LegacyGlobalConfig::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(
+            legacy.authority,
+            accounts.authority.key(),
+            PumpFunError::Unauthorized
+        );
+
+        // Cover the rent of the larger account before growing it
+        let rent_exempt = Rent::get()?.minimum_balance(GlobalConfig::SIZE);
+        let top_up = rent_exempt.saturating_sub(config_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: accounts.authority.to_account_info(),
+                        to: config_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        config_info.realloc(GlobalConfig::SIZE, true)?;
+        let global_config = GlobalConfig::from_legacy(&legacy);
+        global_config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+        emit!(ConfigMigrated {
+            authority: global_config.authority,
+            old_size: LegacyGlobalConfig::SIZE as u64,
+            new_size: GlobalConfig::SIZE as u64,
+            params: global_config.params(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub authority: Pubkey,
+    /// Account size before the upgrade, in bytes
+    pub old_size: u64,
+    /// Account size after the upgrade, in bytes
+    pub new_size: u64,
+    /// Protocol parameters the upgraded config starts with
+    pub params: ConfigParams,
+    pub timestamp: i64,
+}
@@ -1,11 +1,49 @@
 pub mod initialize;
 pub mod create;
 pub mod buy;
+pub mod buy_exact_tokens;
 pub mod sell;
+pub mod sell_for_exact_sol;
 pub mod complete;
+pub mod withdraw_for_migration;
+pub mod close_curve;
+pub mod swap;
+pub mod add_liquidity;
+pub mod remove_liquidity;
+pub mod unlock_liquidity;
+pub mod update_config;
+pub mod migrate_config;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod cancel_authority_transfer;
+pub mod set_paused;
+pub mod set_trading_halted;
+pub mod quote_buy;
+pub mod quote_sell;
+pub mod quote_price;
 
+// Handlers are `pub(crate)` and called through their module from lib.rs, so
+// these re-export only the Accounts structs, parameter types and events.
+// `buy_exact_tokens` and `sell_for_exact_sol` only add handlers to `Buy` and
+// `Sell`, so they have nothing to re-export.
 pub use initialize::*;
 pub use create::*;
 pub use buy::*;
 pub use sell::*;
 pub use complete::*;
+pub use withdraw_for_migration::*;
+pub use close_curve::*;
+pub use swap::*;
+pub use add_liquidity::*;
+pub use remove_liquidity::*;
+pub use unlock_liquidity::*;
+pub use update_config::*;
+pub use migrate_config::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use cancel_authority_transfer::*;
+pub use set_paused::*;
+pub use set_trading_halted::*;
+pub use quote_buy::*;
+pub use quote_sell::*;
+pub use quote_price::*;
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Propose a new protocol authority
+///
+/// First step of a two-step authority transfer. The proposed key is stored
+/// as `pending_authority` and gains no control until it signs
+/// `accept_authority`. Proposing again replaces any earlier proposal.
+///
+/// Accounts:
+/// - authority: Current protocol authority (signer)
+/// - global_config: Global configuration account (PDA)
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ PumpFunError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    ProposeAuthority::execute(ctx, new_authority)
+}
+
+impl<'info> ProposeAuthority<'info> {
+    fn execute(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        let clock = Clock::get()?;
+
+        global_config.pending_authority = Some(new_authority);
+
+        emit!(AuthorityProposed {
+            authority: global_config.authority,
+            pending_authority: new_authority,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+use crate::constants::*;
+
+/// Read-only accounts for the quote instructions
+///
+/// Shared by `quote_buy`, `quote_sell` and `quote_price`. Nothing is written,
+/// so quotes can be taken with `simulateTransaction` or through CPI from
+/// another program; the result is returned via `set_return_data`.
+///
+/// Accounts:
+/// - bonding_curve: Bonding curve state account
+/// - global_config: Global protocol configuration (supplies the fee)
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+/// Result of pricing a buy of `sol_in` lamports
+///
+/// `buy` executes exactly these amounts, so a quote matches the trade as
+/// long as the curve and fee are unchanged in between. A buy that would pass
+/// the curve's graduation point is filled only up to it; the SOL not needed
+/// for the fill is reported in `sol_refunded` and never leaves the buyer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// Total SOL paid by the buyer (less than requested if capped at graduation)
+    pub sol_in: u64,
+    /// Requested SOL left with the buyer because the buy was capped
+    pub sol_refunded: u64,
+    /// SOL sent to the treasury
+    pub protocol_fee: u64,
+    /// SOL deposited in the curve vault and priced on the curve
+    pub sol_to_curve: u64,
+    /// Tokens received by the buyer
+    pub tokens_out: u64,
+    /// Spot price increase in basis points of the pre-trade price
+    pub price_impact_bps: u64,
+    /// Virtual SOL reserve after the trade
+    pub virtual_sol_reserve: u64,
+    /// Virtual token reserve after the trade
+    pub virtual_token_reserve: u64,
+}
+
+impl BuyQuote {
+    /// Price a buy of `sol_requested` lamports against the curve's current reserves
+    pub fn new(bonding_curve: &BondingCurve, fee_bps: u16, sol_requested: u64) -> Result<Self> {
+        // Take the protocol fee on the SOL side, then price the rest on the curve
+        let (protocol_fee, sol_to_curve) = split_fee(sol_requested, fee_bps)?;
+
+        let pricing = bonding_curve.pricing();
+        let graduation_cap = bonding_curve.graduation_cap()?;
+        let (sol_in, protocol_fee, sol_to_curve, tokens_out) = match graduation_cap {
+            // Final buy: fill only up to graduation and charge only for that,
+            // grossed up for the fee as in `buy_exact_tokens`
+            Some((cap_sol, cap_tokens)) if sol_to_curve >= cap_sol => {
+                let charged = calculate_gross_for_net(cap_sol, fee_bps)?.min(sol_requested);
+                let (protocol_fee, sol_to_curve) = split_fee(charged, fee_bps)?;
+                (charged, protocol_fee, sol_to_curve, cap_tokens)
+            }
+            _ => (sol_requested, protocol_fee, sol_to_curve, pricing.tokens_out(sol_to_curve)?),
+        };
+
+        // Apply the trade to a copy of the curve to read the post-trade state
+        let mut after = bonding_curve.clone();
+        after.update_after_buy(sol_to_curve, tokens_out)?;
+
+        let price_impact_bps = calculate_price_impact_bps(
+            pricing.spot_price()?,
+            after.pricing().spot_price()?,
+        )?;
+
+        Ok(Self {
+            sol_in,
+            sol_refunded: sol_requested - sol_in,
+            protocol_fee,
+            sol_to_curve,
+            tokens_out,
+            price_impact_bps,
+            virtual_sol_reserve: after.virtual_sol_reserve,
+            virtual_token_reserve: after.virtual_token_reserve,
+        })
+    }
+
+    /// Scale a buyer's `min_tokens_out` to the filled part of the buy
+    ///
+    /// A capped final buy fills only `sol_in` of the requested SOL, so the
+    /// slippage bound is applied pro rata: the buyer still gets at least the
+    /// average price they accepted, and the fill is not rejected for being partial.
+    /// Formula: floor(min_tokens_out * sol_in / (sol_in + sol_refunded))
+    pub fn min_tokens_for_fill(&self, min_tokens_out: u64) -> Result<u64> {
+        if self.sol_refunded == 0 {
+            return Ok(min_tokens_out);
+        }
+
+        let sol_requested = self.sol_in
+            .checked_add(self.sol_refunded)
+            .ok_or(PumpFunError::MathOverflow)?;
+
+        to_u64(div_floor(
+            (min_tokens_out as u128)
+                .checked_mul(self.sol_in as u128)
+                .ok_or(PumpFunError::MathOverflow)?,
+            sol_requested as u128,
+        )?)
+    }
+}
+
+pub(crate) fn handler(ctx: Context<Quote>, sol_in: u64) -> Result<BuyQuote> {
+    Quote::execute_buy(ctx, sol_in)
+}
+
+impl<'info> Quote<'info> {
+    fn execute_buy(ctx: Context<Quote>, sol_in: u64) -> Result<BuyQuote> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+
+        // Same input checks as `buy`, so a quote never succeeds where it would fail
+        require!(!bonding_curve.completed, PumpFunError::AlreadyCompleted);
+        require!(sol_in >= MIN_SOL_AMOUNT, PumpFunError::MinSolAmountNotMet);
+        require!(sol_in > 0, PumpFunError::InvalidAmount);
+
+        BuyQuote::new(bonding_curve, ctx.accounts.global_config.protocol_fee_bps, sol_in)
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::curve::CurveKind;
+use super::quote_buy::Quote;
+
+/// Current pricing state of a bonding curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PriceQuote {
+    /// Spot price in lamports per whole token, scaled by `PRICE_SCALE`
+    pub spot_price: u64,
+    /// Protocol fee applied to trades, in basis points
+    pub protocol_fee_bps: u16,
+    pub virtual_sol_reserve: u64,
+    pub virtual_token_reserve: u64,
+    pub real_sol_reserve: u64,
+    pub tokens_sold: u64,
+    pub completed: bool,
+    pub curve_kind: CurveKind,
+}
+
+pub(crate) fn handler(ctx: Context<Quote>) -> Result<PriceQuote> {
+    Quote::execute_price(ctx)
+}
+
+impl<'info> Quote<'info> {
+    fn execute_price(ctx: Context<Quote>) -> Result<PriceQuote> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+
+        Ok(PriceQuote {
+            spot_price: bonding_curve.pricing().spot_price()?,
+            protocol_fee_bps: ctx.accounts.global_config.protocol_fee_bps,
+            virtual_sol_reserve: bonding_curve.virtual_sol_reserve,
+            virtual_token_reserve: bonding_curve.virtual_token_reserve,
+            real_sol_reserve: bonding_curve.real_sol_reserve,
+            tokens_sold: bonding_curve.tokens_sold,
+            completed: bonding_curve.completed,
+            curve_kind: bonding_curve.curve_kind,
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+use crate::constants::*;
+use super::quote_buy::Quote;
+
+/// Result of pricing a sell of `tokens_in` tokens
+///
+/// `sell` executes exactly these amounts, so a quote matches the trade as
+/// long as the curve and fee are unchanged in between.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SellQuote {
+    /// Tokens returned to the curve
+    pub tokens_in: u64,
+    /// SOL withdrawn from the curve vault
+    pub sol_from_curve: u64,
+    /// SOL sent to the treasury
+    pub protocol_fee: u64,
+    /// SOL received by the seller
+    pub sol_out: u64,
+    /// Spot price decrease in basis points of the pre-trade price
+    pub price_impact_bps: u64,
+    /// Virtual SOL reserve after the trade
+    pub virtual_sol_reserve: u64,
+    /// Virtual token reserve after the trade
+    pub virtual_token_reserve: u64,
+}
+
+impl SellQuote {
+    /// Price a sell of `tokens_in` tokens against the curve's current reserves
+    pub fn new(bonding_curve: &BondingCurve, fee_bps: u16, tokens_in: u64) -> Result<Self> {
+        // Price the tokens on the curve, then take the protocol fee on the SOL side
+        let pricing = bonding_curve.pricing();
+        let sol_from_curve = pricing.sol_out(tokens_in)?;
+
+        let (protocol_fee, sol_out) = split_fee(sol_from_curve, fee_bps)?;
+
+        // Apply the trade to a copy of the curve to read the post-trade state
+        let mut after = bonding_curve.clone();
+        after.update_after_sell(tokens_in, sol_from_curve)?;
+
+        let price_impact_bps = calculate_price_impact_bps(
+            pricing.spot_price()?,
+            after.pricing().spot_price()?,
+        )?;
+
+        Ok(Self {
+            tokens_in,
+            sol_from_curve,
+            protocol_fee,
+            sol_out,
+            price_impact_bps,
+            virtual_sol_reserve: after.virtual_sol_reserve,
+            virtual_token_reserve: after.virtual_token_reserve,
+        })
+    }
+}
+
+pub(crate) fn handler(ctx: Context<Quote>, tokens_in: u64) -> Result<SellQuote> {
+    Quote::execute_sell(ctx, tokens_in)
+}
+
+impl<'info> Quote<'info> {
+    fn execute_sell(ctx: Context<Quote>, tokens_in: u64) -> Result<SellQuote> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+
+        require!(!bonding_curve.completed, PumpFunError::AlreadyCompleted);
+        require!(tokens_in > 0, PumpFunError::InvalidAmount);
+
+        let quote = SellQuote::new(bonding_curve, ctx.accounts.global_config.protocol_fee_bps, tokens_in)?;
+
+        // The vault can only pay out what it holds
+        require!(
+            bonding_curve.real_sol_reserve >= quote.sol_from_curve,
+            PumpFunError::InsufficientLiquidity
+        );
+
+        Ok(quote)
+    }
+}
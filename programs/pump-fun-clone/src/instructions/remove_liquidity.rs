@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+
+use crate::errors::PumpFunError;
+use super::add_liquidity::Liquidity;
+
+/// Burn LP tokens for a share of a graduated curve's pool
+///
+/// Withdrawal counterpart of `add_liquidity`, using the same accounts.
+/// The provider receives their share of both reserves, rounded down:
+///
+/// sol_out = floor(lp_amount * sol_reserve / lp_supply)
+/// tokens_out = floor(lp_amount * token_reserve / lp_supply)
+///
+/// The last LP tokens in circulation cannot be burned, so the pool always
+/// keeps liquidity. Fails with `SlippageExceeded` if either output is below
+/// its minimum.
+pub(crate) fn handler(
+    ctx: Context<Liquidity>,
+    lp_amount: u64,
+    min_sol_out: u64,
+    min_tokens_out: u64,
+) -> Result<()> {
+    Liquidity::execute_remove(ctx, lp_amount, min_sol_out, min_tokens_out)
+}
+
+impl<'info> Liquidity<'info> {
+    fn execute_remove(
+        ctx: Context<Liquidity>,
+        lp_amount: u64,
+        min_sol_out: u64,
+        min_tokens_out: u64,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+
+        let (sol_out, tokens_out) = accounts.pool.quote_withdrawal(lp_amount)?;
+
+        // Slippage checks
+        require!(
+            sol_out >= min_sol_out && tokens_out >= min_tokens_out,
+            PumpFunError::SlippageExceeded
+        );
+
+        // Burn the provider's LP tokens
+        let cpi_accounts = token_2022::Burn {
+            mint: accounts.lp_mint.to_account_info(),
+            from: accounts.provider_lp_account.to_account_info(),
+            authority: accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::burn(cpi_ctx, lp_amount)?;
+
+        // Tokens from the pool's token account
+        let mint_key = accounts.pool.mint;
+        let seeds = &[
+            b"pool",
+            mint_key.as_ref(),
+            &[accounts.pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: accounts.pool_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.provider_token_account.to_account_info(),
+            authority: accounts.pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022::transfer_checked(cpi_ctx, tokens_out, accounts.mint.decimals)?;
+
+        // SOL from the pool account, which is program-owned
+        **accounts.pool.to_account_info().try_borrow_mut_lamports()? -= sol_out;
+        **accounts.provider.to_account_info().try_borrow_mut_lamports()? += sol_out;
+
+        // Update pool state
+        let pool = &mut accounts.pool;
+        pool.update_after_withdrawal(sol_out, tokens_out, lp_amount)?;
+        pool.assert_solvent(&pool.to_account_info())?;
+
+        emit!(LiquidityRemoved {
+            mint: pool.mint,
+            provider: accounts.provider.key(),
+            lp_in: lp_amount,
+            sol_out,
+            tokens_out,
+            sol_reserve: pool.sol_reserve,
+            token_reserve: pool.token_reserve,
+            lp_supply: pool.lp_supply,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    /// LP tokens burned
+    pub lp_in: u64,
+    pub sol_out: u64,
+    pub tokens_out: u64,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    pub lp_supply: u64,
+    pub timestamp: i64,
+}
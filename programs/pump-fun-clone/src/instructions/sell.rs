@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{Token2022, Mint, TokenAccount};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
 
 use crate::state::*;
 use crate::errors::PumpFunError;
-use crate::constants::*;
+use super::quote_sell::SellQuote;
 
 /// Sell tokens back to the bonding curve
 /// 
@@ -14,13 +15,17 @@ use crate::constants::*;
 /// - k = constant product
 /// 
 /// When selling: tokens in → SOL out
-/// New reserves: (x - sol_out) * (y + tokens_in) = k
+/// The curve releases `sol_from_curve` from the vault; the protocol fee is
+/// taken from that and the remainder (`sol_out`) is paid to the seller.
+/// New reserves: (x - sol_from_curve) * (y + tokens_in) = k
 /// 
 /// Accounts:
-/// - seller: Token seller (signer, burns tokens)
+/// - seller: Token seller (signer, returns tokens to the curve)
 /// - bonding_curve: Bonding curve state account
 /// - mint: Token mint account
-/// - seller_token_account: Seller's token account (tokens burned from here)
+/// - seller_token_account: Seller's token account (tokens sent from here)
+/// - curve_token_account: Bonding curve's token account (receives tokens)
+/// - curve_vault: Per-curve SOL vault (pays out SOL)
 /// - global_config: Global protocol configuration
 /// - treasury: Treasury account (receives protocol fees)
 /// - token_program: Token-2022 program
@@ -35,26 +40,44 @@ pub struct Sell<'info> {
         mut,
         seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
         bump = bonding_curve.bump,
-        constraint = !bonding_curve.completed @ PumpFunError::AlreadyCompleted
+        constraint = !bonding_curve.completed @ PumpFunError::AlreadyCompleted,
+        constraint = !bonding_curve.trading_halted @ PumpFunError::TradingHalted
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
     #[account(
-        mut,
         constraint = mint.key() == bonding_curve.mint @ PumpFunError::InvalidTokenMint
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::owner = seller,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_vault", bonding_curve.mint.as_ref()],
+        bump = curve_vault.bump
+    )]
+    pub curve_vault: Account<'info, CurveVault>,
+
+    #[account(
+        mut,
         seeds = [b"global_config"],
-        bump
+        bump,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
@@ -71,7 +94,7 @@ pub struct Sell<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<Sell>,
     tokens_in: u64,
     min_sol_out: u64,
@@ -85,75 +108,81 @@ impl<'info> Sell<'info> {
         tokens_in: u64,
         min_sol_out: u64,
     ) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let seller = &ctx.accounts.seller;
-        let clock = Clock::get()?;
+        let fee_bps = ctx.accounts.global_config.protocol_fee_bps;
 
         // Validate input
         require!(tokens_in > 0, PumpFunError::InvalidAmount);
 
-        // Check seller has enough tokens
+        // Same pricing as `quote_sell`: curve output first, fee on the SOL side
+        let quote = SellQuote::new(&ctx.accounts.bonding_curve, fee_bps, tokens_in)?;
+
+        // Slippage check against what the seller actually receives
         require!(
-            ctx.accounts.seller_token_account.amount >= tokens_in,
-            PumpFunError::InvalidAmount
+            quote.sol_out >= min_sol_out,
+            PumpFunError::SlippageExceeded
         );
 
-        // Calculate SOL out using bonding curve formula
-        let sol_out = calculate_sol_out(
-            tokens_in,
-            bonding_curve.virtual_sol_reserve,
-            bonding_curve.virtual_token_reserve,
-        )?;
+        ctx.accounts.settle(tokens_in, quote.sol_from_curve, quote.protocol_fee, quote.sol_out)
+    }
 
-        // Slippage check
+    /// Move funds for a priced sell and update curve state
+    ///
+    /// Shared by `sell` and `sell_for_exact_sol` once the amounts are known:
+    /// `tokens_in` go to the curve's token account, and `sol_from_curve` leaves
+    /// the curve vault split into `protocol_fee` for the treasury and
+    /// `sol_out` for the seller.
+    pub(crate) fn settle(
+        &mut self,
+        tokens_in: u64,
+        sol_from_curve: u64,
+        protocol_fee: u64,
+        sol_out: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // Check seller has enough tokens
         require!(
-            sol_out >= min_sol_out,
-            PumpFunError::SlippageExceeded
+            self.seller_token_account.amount >= tokens_in,
+            PumpFunError::InvalidAmount
         );
 
-        // Calculate protocol fee
-        let protocol_fee = (sol_out as u128)
-            .checked_mul(PROTOCOL_FEE_BPS as u128)
-            .ok_or(PumpFunError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(PumpFunError::MathOverflow)? as u64;
-
-        let sol_after_fee = sol_out
-            .checked_sub(protocol_fee)
-            .ok_or(PumpFunError::MathOverflow)?;
-
-        // Burn tokens from seller
-        let cpi_accounts = anchor_spl::token_2022::Burn {
-            mint: ctx.accounts.mint.to_account_info(),
-            from: ctx.accounts.seller_token_account.to_account_info(),
-            authority: seller.to_account_info(),
+        // Return tokens from seller to the curve's token account
+        let cpi_accounts = anchor_spl::token_2022::TransferChecked {
+            from: self.seller_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.curve_token_account.to_account_info(),
+            authority: self.seller.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_program = self.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        anchor_spl::token_2022::burn(cpi_ctx, tokens_in)?;
+        anchor_spl::token_2022::transfer_checked(cpi_ctx, tokens_in, self.mint.decimals)?;
 
-        // Transfer SOL to seller (from treasury/real reserves)
-        // In reality, this comes from accumulated SOL reserves
+        // Pay out of this curve's vault only: SOL to seller, fee to treasury
+        // The vault is program-owned, so its lamports can be debited directly
+        let bonding_curve = &mut self.bonding_curve;
         require!(
-            ctx.accounts.treasury.lamports() >= sol_after_fee,
+            bonding_curve.real_sol_reserve >= sol_from_curve,
             PumpFunError::InsufficientLiquidity
         );
 
-        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? -= sol_after_fee;
-        **seller.to_account_info().try_borrow_mut_lamports()? += sol_after_fee;
-
-        // Protocol fee stays in treasury
-        // (already accounted for in sol_after_fee calculation)
+        **self.curve_vault.to_account_info().try_borrow_mut_lamports()? -= sol_from_curve;
+        **self.seller.to_account_info().try_borrow_mut_lamports()? += sol_out;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += protocol_fee;
 
         // Update bonding curve state
-        bonding_curve.update_after_sell(tokens_in, sol_out);
+        bonding_curve.update_after_sell(tokens_in, sol_from_curve)?;
+        bonding_curve.record_fee(protocol_fee)?;
+        self.global_config.record_fee(protocol_fee)?;
+        bonding_curve.check_invariants(&self.curve_vault.to_account_info())?;
 
         // Emit sell event
         emit!(TokenSold {
             mint: bonding_curve.mint,
-            seller: seller.key(),
+            seller: self.seller.key(),
             tokens_in,
-            sol_out: sol_after_fee,
+            sol_from_curve,
+            protocol_fee,
+            sol_out,
             virtual_sol_reserve: bonding_curve.virtual_sol_reserve,
             virtual_token_reserve: bonding_curve.virtual_token_reserve,
             timestamp: clock.unix_timestamp,
@@ -168,6 +197,11 @@ pub struct TokenSold {
     pub mint: Pubkey,
     pub seller: Pubkey,
     pub tokens_in: u64,
+    /// SOL withdrawn from the curve vault (`protocol_fee + sol_out`)
+    pub sol_from_curve: u64,
+    /// SOL sent to the treasury
+    pub protocol_fee: u64,
+    /// SOL received by the seller
     pub sol_out: u64,
     pub virtual_sol_reserve: u64,
     pub virtual_token_reserve: u64,
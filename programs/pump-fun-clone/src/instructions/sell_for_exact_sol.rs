@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::PumpFunError;
+use crate::constants::*;
+use super::sell::Sell;
+
+/// Sell tokens to the bonding curve for an exact amount of SOL
+///
+/// Exact-output counterpart of `sell`, using the same accounts. The SOL the
+/// curve must release is `sol_out` grossed up for the protocol fee, and the
+/// tokens needed for it come from the curve kind's `tokens_in` (for constant
+/// product, `calculate_tokens_in`). Both steps round up in the pool's favour.
+///
+/// sol_from_curve = ceil(sol_out * 10000 / (10000 - fee_bps))
+/// Constant product: tokens_in = ceil(k / (x - sol_from_curve)) - y
+///
+/// The fee is charged on `sol_from_curve` with `split_fee`, exactly as `sell`
+/// charges it, and the seller receives the rest: `sol_out`, plus any lamport
+/// the gross-up rounded over. The trade fails with `SlippageExceeded` if
+/// `tokens_in` exceeds `max_tokens_in`.
+pub(crate) fn handler(
+    ctx: Context<Sell>,
+    sol_out: u64,
+    max_tokens_in: u64,
+) -> Result<()> {
+    Sell::execute_for_exact_sol(ctx, sol_out, max_tokens_in)
+}
+
+impl<'info> Sell<'info> {
+    fn execute_for_exact_sol(
+        ctx: Context<Sell>,
+        sol_out: u64,
+        max_tokens_in: u64,
+    ) -> Result<()> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let fee_bps = ctx.accounts.global_config.protocol_fee_bps;
+
+        // Validate input
+        require!(sol_out > 0, PumpFunError::InvalidAmount);
+
+        // SOL the curve must release so the seller nets at least `sol_out`,
+        // split exactly as `sell` does; rounding surplus goes to the seller
+        let sol_from_curve = calculate_gross_for_net(sol_out, fee_bps)?;
+        let (protocol_fee, sol_to_seller) = split_fee(sol_from_curve, fee_bps)?;
+        require!(sol_to_seller >= sol_out, PumpFunError::MathOverflow);
+
+        // Tokens the curve needs in exchange
+        let tokens_in = bonding_curve.pricing().tokens_in(sol_from_curve)?;
+
+        // Slippage check
+        require!(
+            tokens_in <= max_tokens_in,
+            PumpFunError::SlippageExceeded
+        );
+
+        ctx.accounts.settle(tokens_in, sol_from_curve, protocol_fee, sol_to_seller)
+    }
+}
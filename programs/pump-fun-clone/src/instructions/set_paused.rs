@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Pause or resume the whole protocol
+///
+/// Emergency switch for the protocol authority. While `paused` is set,
+/// `create`, `buy`, `sell` and `complete` all fail with `ProtocolPaused`.
+///
+/// Accounts:
+/// - authority: Protocol authority (signer)
+/// - global_config: Global configuration account (PDA)
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ PumpFunError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    SetPaused::execute(ctx, paused)
+}
+
+impl<'info> SetPaused<'info> {
+    fn execute(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        let clock = Clock::get()?;
+
+        global_config.paused = paused;
+
+        emit!(PauseUpdated {
+            authority: global_config.authority,
+            paused,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Halt or resume trading on a single bonding curve
+///
+/// Lets the protocol authority isolate one curve without pausing the whole
+/// protocol. While `trading_halted` is set, `buy`, `sell` and `complete`
+/// on this curve fail with `TradingHalted`.
+///
+/// Accounts:
+/// - authority: Protocol authority (signer)
+/// - global_config: Global configuration account (PDA)
+/// - bonding_curve: Bonding curve state account
+#[derive(Accounts)]
+pub struct SetTradingHalted<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ PumpFunError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+pub(crate) fn handler(ctx: Context<SetTradingHalted>, halted: bool) -> Result<()> {
+    SetTradingHalted::execute(ctx, halted)
+}
+
+impl<'info> SetTradingHalted<'info> {
+    fn execute(ctx: Context<SetTradingHalted>, halted: bool) -> Result<()> {
+        let bonding_curve = &mut ctx.accounts.bonding_curve;
+        let clock = Clock::get()?;
+
+        bonding_curve.trading_halted = halted;
+
+        emit!(TradingHaltUpdated {
+            mint: bonding_curve.mint,
+            authority: ctx.accounts.authority.key(),
+            halted,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TradingHaltUpdated {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub halted: bool,
+    pub timestamp: i64,
+}
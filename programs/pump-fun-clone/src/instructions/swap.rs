@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Swap SOL and tokens on a graduated curve's pool
+///
+/// Formula: constant product (x * y = k) over the pool's real reserves.
+/// The pool fee is taken from `amount_in` and stays in the input reserve,
+/// so k grows with every swap and the fee accrues to LP holders.
+///
+/// amount_out = reserve_out - ceil(k / (reserve_in + amount_in - fee))
+///
+/// Fails with `SlippageExceeded` if `amount_out` is below `min_amount_out`.
+///
+/// Accounts:
+/// - user: Trader (signer, pays SOL or tokens)
+/// - pool: Graduation pool state account (holds the SOL reserve)
+/// - mint: Token mint account
+/// - user_token_account: Trader's token account
+/// - pool_token_account: Pool's token account (holds the token reserve)
+/// - global_config: Global protocol configuration
+/// - token_program: Token-2022 program
+/// - system_program: System program
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        constraint = mint.key() == pool.mint @ PumpFunError::InvalidTokenMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<Swap>,
+    direction: SwapDirection,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    Swap::execute(ctx, direction, amount_in, min_amount_out)
+}
+
+impl<'info> Swap<'info> {
+    fn execute(
+        ctx: Context<Swap>,
+        direction: SwapDirection,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+
+        // Validate input
+        require!(amount_in > 0, PumpFunError::InvalidAmount);
+
+        let (fee, amount_out) = accounts.pool.quote_swap(direction, amount_in)?;
+
+        // Slippage check
+        require!(
+            amount_out >= min_amount_out,
+            PumpFunError::SlippageExceeded
+        );
+
+        let mint_key = accounts.pool.mint;
+        let seeds = &[
+            b"pool",
+            mint_key.as_ref(),
+            &[accounts.pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        match direction {
+            SwapDirection::SolToToken => {
+                // The user is system-owned, so SOL goes in through the System Program
+                system_program::transfer(
+                    CpiContext::new(
+                        accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: accounts.user.to_account_info(),
+                            to: accounts.pool.to_account_info(),
+                        },
+                    ),
+                    amount_in,
+                )?;
+
+                let cpi_accounts = token_2022::TransferChecked {
+                    from: accounts.pool_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.user_token_account.to_account_info(),
+                    authority: accounts.pool.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer,
+                );
+                token_2022::transfer_checked(cpi_ctx, amount_out, accounts.mint.decimals)?;
+            }
+            SwapDirection::TokenToSol => {
+                let cpi_accounts = token_2022::TransferChecked {
+                    from: accounts.user_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.pool_token_account.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(
+                    accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                );
+                token_2022::transfer_checked(cpi_ctx, amount_in, accounts.mint.decimals)?;
+
+                // The pool is program-owned, so its lamports can be debited directly
+                **accounts.pool.to_account_info().try_borrow_mut_lamports()? -= amount_out;
+                **accounts.user.to_account_info().try_borrow_mut_lamports()? += amount_out;
+            }
+        }
+
+        // Update pool state
+        let pool = &mut accounts.pool;
+        pool.update_after_swap(direction, amount_in, amount_out)?;
+        pool.assert_solvent(&pool.to_account_info())?;
+
+        emit!(PoolSwapped {
+            mint: pool.mint,
+            user: accounts.user.key(),
+            direction,
+            amount_in,
+            fee,
+            amount_out,
+            sol_reserve: pool.sol_reserve,
+            token_reserve: pool.token_reserve,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PoolSwapped {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub direction: SwapDirection,
+    /// SOL or tokens paid in, fee included
+    pub amount_in: u64,
+    /// Pool fee taken from `amount_in`, left in the pool
+    pub fee: u64,
+    /// Tokens or SOL paid out
+    pub amount_out: u64,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    pub timestamp: i64,
+}
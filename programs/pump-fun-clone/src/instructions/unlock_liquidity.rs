@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+
+/// Release a pool's locked LP tokens to the creator
+///
+/// Only the lock's beneficiary (the curve's creator) may call this, once,
+/// after `unlock_at`. Fails with `LiquidityStillLocked` before then, or
+/// always if the creator chose a permanent lock at `create`.
+///
+/// Accounts:
+/// - creator: Lock beneficiary (signer)
+/// - lp_lock: LP lock state account (PDA)
+/// - lp_mint: Pool LP mint
+/// - lock_lp_account: Lock's LP token account (sends the LP tokens)
+/// - creator_lp_account: Creator's LP token account (receives the LP tokens)
+/// - token_program: Token-2022 program
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock", lp_lock.mint.as_ref()],
+        bump = lp_lock.bump,
+        constraint = lp_lock.beneficiary == creator.key() @ PumpFunError::Unauthorized,
+        has_one = lp_mint @ PumpFunError::InvalidTokenMint
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock,
+        associated_token::token_program = token_program,
+    )]
+    pub lock_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub(crate) fn handler(ctx: Context<UnlockLiquidity>) -> Result<()> {
+    UnlockLiquidity::execute(ctx)
+}
+
+impl<'info> UnlockLiquidity<'info> {
+    fn execute(ctx: Context<UnlockLiquidity>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+
+        accounts.lp_lock.assert_unlockable(clock.unix_timestamp)?;
+
+        let amount = accounts.lock_lp_account.amount;
+        let seeds = &[
+            b"lp_lock",
+            accounts.lp_lock.mint.as_ref(),
+            &[accounts.lp_lock.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: accounts.lock_lp_account.to_account_info(),
+            mint: accounts.lp_mint.to_account_info(),
+            to: accounts.creator_lp_account.to_account_info(),
+            authority: accounts.lp_lock.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, accounts.lp_mint.decimals)?;
+
+        let lp_lock = &mut accounts.lp_lock;
+        lp_lock.unlocked = true;
+
+        emit!(LiquidityUnlocked {
+            mint: lp_lock.mint,
+            lp_lock: lp_lock.key(),
+            beneficiary: lp_lock.beneficiary,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct LiquidityUnlocked {
+    pub mint: Pubkey,
+    pub lp_lock: Pubkey,
+    pub beneficiary: Pubkey,
+    /// LP tokens released to the beneficiary
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::migration::MigrationTarget;
+use crate::errors::PumpFunError;
+
+/// Update tunable protocol parameters stored in the global configuration
+///
+/// Only the protocol authority may call this. Every field of
+/// `UpdateConfigParams` is optional; omitted fields keep their current value.
+/// The resulting parameter set is validated against protocol bounds before
+/// being written.
+///
+/// Accounts:
+/// - authority: Protocol authority (signer)
+/// - global_config: Global configuration account (PDA)
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ PumpFunError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+/// Optional parameter overrides for `update_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct UpdateConfigParams {
+    /// New protocol fee in basis points
+    pub protocol_fee_bps: Option<u16>,
+    /// New token creation fee in lamports
+    pub creation_fee: Option<u64>,
+    /// New maximum token name length in bytes
+    pub max_name_len: Option<u16>,
+    /// New maximum token symbol length in bytes
+    pub max_symbol_len: Option<u16>,
+    /// New maximum metadata URI length in bytes
+    pub max_uri_len: Option<u16>,
+    /// New bitmask of curve kinds allowed at `create`
+    pub enabled_curve_kinds: Option<u8>,
+    /// New lowest start price for linear/exponential curves
+    pub min_start_price: Option<u64>,
+    /// New highest end price for linear/exponential curves
+    pub max_end_price: Option<u64>,
+    /// New lower bounds for per-curve parameters
+    pub min_curve_params: Option<CurveParams>,
+    /// New upper bounds for per-curve parameters
+    pub max_curve_params: Option<CurveParams>,
+    /// New graduation criterion for curves created from now on
+    pub graduation_criterion: Option<GraduationCriterion>,
+    /// New swap fee for pools created at graduation from now on
+    pub pool_fee_bps: Option<u16>,
+    /// New shortest LP lock in seconds for curves created from now on
+    pub min_lp_lock_duration: Option<i64>,
+    /// New venue for liquidity of curves completed from now on
+    pub migration_target: Option<MigrationTarget>,
+    /// New fee taken from the real SOL reserve at `complete`
+    pub migration_fee: Option<u64>,
+    /// New part of the migration fee paid to the completer
+    pub completer_reward: Option<u64>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    UpdateConfig::execute(ctx, params)
+}
+
+impl<'info> UpdateConfig<'info> {
+    fn execute(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        let clock = Clock::get()?;
+
+        let old = global_config.params();
+        let mut new = old.clone();
+
+        if let Some(protocol_fee_bps) = params.protocol_fee_bps {
+            new.protocol_fee_bps = protocol_fee_bps;
+        }
+        if let Some(creation_fee) = params.creation_fee {
+            new.creation_fee = creation_fee;
+        }
+        if let Some(max_name_len) = params.max_name_len {
+            new.max_name_len = max_name_len;
+        }
+        if let Some(max_symbol_len) = params.max_symbol_len {
+            new.max_symbol_len = max_symbol_len;
+        }
+        if let Some(max_uri_len) = params.max_uri_len {
+            new.max_uri_len = max_uri_len;
+        }
+        if let Some(enabled_curve_kinds) = params.enabled_curve_kinds {
+            new.enabled_curve_kinds = enabled_curve_kinds;
+        }
+        if let Some(min_start_price) = params.min_start_price {
+            new.min_start_price = min_start_price;
+        }
+        if let Some(max_end_price) = params.max_end_price {
+            new.max_end_price = max_end_price;
+        }
+        if let Some(min_curve_params) = params.min_curve_params {
+            new.min_curve_params = min_curve_params;
+        }
+        if let Some(max_curve_params) = params.max_curve_params {
+            new.max_curve_params = max_curve_params;
+        }
+        if let Some(graduation_criterion) = params.graduation_criterion {
+            new.graduation_criterion = graduation_criterion;
+        }
+        if let Some(pool_fee_bps) = params.pool_fee_bps {
+            new.pool_fee_bps = pool_fee_bps;
+        }
+        if let Some(min_lp_lock_duration) = params.min_lp_lock_duration {
+            new.min_lp_lock_duration = min_lp_lock_duration;
+        }
+        if let Some(migration_target) = params.migration_target {
+            new.migration_target = migration_target;
+        }
+        if let Some(migration_fee) = params.migration_fee {
+            new.migration_fee = migration_fee;
+        }
+        if let Some(completer_reward) = params.completer_reward {
+            new.completer_reward = completer_reward;
+        }
+
+        // Validates bounds before writing
+        global_config.set_params(&new)?;
+
+        emit!(ConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            old,
+            new,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub old: ConfigParams,
+    pub new: ConfigParams,
+    pub timestamp: i64,
+}
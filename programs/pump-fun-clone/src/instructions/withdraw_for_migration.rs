@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::PumpFunError;
+use crate::migration::MigrationTarget;
+
+/// Move a completed curve's liquidity to the configured migration wallet
+///
+/// The authority-held escrow alternative to `complete`, for migrating with
+/// off-chain tooling. Only available while `GlobalConfig.migration_target`
+/// is `MigrationTarget::Wallet`, and only to the protocol authority. It runs
+/// once per graduated curve and:
+/// 1. Burns the allocated tokens the curve never sold
+/// 2. Moves the curve's liquidity tokens (`TOTAL_SUPPLY - token_allocation`)
+///    to the wallet's token account, which must already exist
+/// 3. Moves the curve's whole `real_sol_reserve` to the wallet
+/// 4. Records both amounts on the bonding curve and marks it migrated
+///
+/// No migration fee is taken, and no LP is locked: what happens to the
+/// liquidity afterwards is up to the wallet's owner.
+///
+/// Accounts:
+/// - authority: Protocol authority (signer)
+/// - bonding_curve: Bonding curve state account
+/// - mint: Token mint account
+/// - curve_token_account: Bonding curve's token account (sends liquidity tokens)
+/// - curve_vault: Per-curve SOL vault (sends the real SOL reserve)
+/// - migration_wallet: Wallet configured in `MigrationTarget::Wallet` (receives SOL)
+/// - migration_wallet_token_account: Wallet's token account (ATA, receives tokens)
+/// - global_config: Global protocol configuration
+/// - token_program: Token-2022 program
+#[derive(Accounts)]
+pub struct WithdrawForMigration<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        constraint = !bonding_curve.migrated @ PumpFunError::AlreadyMigrated,
+        constraint = !bonding_curve.trading_halted @ PumpFunError::TradingHalted
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == bonding_curve.mint @ PumpFunError::InvalidTokenMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_vault", bonding_curve.mint.as_ref()],
+        bump = curve_vault.bump
+    )]
+    pub curve_vault: Account<'info, CurveVault>,
+
+    /// CHECK: Checked against `GlobalConfig.migration_target` in the handler
+    #[account(mut)]
+    pub migration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = migration_wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub migration_wallet_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ PumpFunError::Unauthorized,
+        constraint = !global_config.paused @ PumpFunError::ProtocolPaused
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub(crate) fn handler(ctx: Context<WithdrawForMigration>) -> Result<()> {
+    WithdrawForMigration::execute(ctx)
+}
+
+impl<'info> WithdrawForMigration<'info> {
+    fn execute(ctx: Context<WithdrawForMigration>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let clock = Clock::get()?;
+
+        let MigrationTarget::Wallet { wallet } = accounts.global_config.migration_target else {
+            return err!(PumpFunError::InvalidMigrationTarget);
+        };
+        require_keys_eq!(
+            accounts.migration_wallet.key(),
+            wallet,
+            PumpFunError::InvalidMigrationAccounts
+        );
+
+        // Verify the curve has graduated; the final buy usually marks it already
+        require!(
+            accounts.bonding_curve.completed || accounts.bonding_curve.graduation_reached()?,
+            PumpFunError::NotCompleted
+        );
+        if !accounts.bonding_curve.completed {
+            accounts.bonding_curve.complete(&clock);
+        }
+
+        let bonding_curve = &accounts.bonding_curve;
+        let sol_amount = bonding_curve.real_sol_reserve;
+        let token_amount = bonding_curve.liquidity_tokens()?;
+        let unsold_tokens = bonding_curve.unsold_tokens()?;
+
+        let mint_key = bonding_curve.mint;
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bonding_curve.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Burn the unsold part of the allocation so only liquidity tokens remain
+        if unsold_tokens > 0 {
+            let cpi_accounts = token_2022::Burn {
+                mint: accounts.mint.to_account_info(),
+                from: accounts.curve_token_account.to_account_info(),
+                authority: accounts.bonding_curve.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            token_2022::burn(cpi_ctx, unsold_tokens)?;
+        }
+
+        // Move the liquidity tokens to the wallet
+        let cpi_accounts = token_2022::TransferChecked {
+            from: accounts.curve_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.migration_wallet_token_account.to_account_info(),
+            authority: accounts.bonding_curve.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022::transfer_checked(cpi_ctx, token_amount, accounts.mint.decimals)?;
+
+        // Move the real SOL reserve to the wallet
+        // The vault is program-owned, so its lamports can be debited directly
+        **accounts.curve_vault.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+        **accounts.migration_wallet.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+
+        let bonding_curve = &mut accounts.bonding_curve;
+        bonding_curve.record_migration(sol_amount, token_amount);
+        bonding_curve.assert_vault_solvent(&accounts.curve_vault.to_account_info())?;
+
+        emit!(LiquidityWithdrawn {
+            mint: mint_key,
+            wallet,
+            sol_amount,
+            token_amount,
+            tokens_burned: unsold_tokens,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub mint: Pubkey,
+    /// Migration wallet the liquidity was sent to
+    pub wallet: Pubkey,
+    /// SOL moved from the curve vault
+    pub sol_amount: u64,
+    /// Liquidity tokens moved from the curve's token account
+    pub token_amount: u64,
+    /// Unsold allocated tokens burned
+    pub tokens_burned: u64,
+    pub timestamp: i64,
+}
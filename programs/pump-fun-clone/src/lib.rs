@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
 pub mod constants;
+pub mod curve;
 pub mod errors;
+pub mod migration;
 pub mod state;
 pub mod instructions;
 
@@ -23,14 +25,18 @@ pub mod pump_fun_clone {
 
     /// Create a new token with bonding curve
     /// 
-    /// Creates a Token-2022 mint with metadata and initializes the bonding curve.
+    /// Creates a Token-2022 mint with metadata and initializes the bonding curve
+    /// with the requested pricing model, launch parameters and LP lock.
     pub fn create(
         ctx: Context<Create>,
         name: String,
         symbol: String,
         uri: String,
+        curve_kind: CurveKind,
+        curve_params: CurveParams,
+        lp_unlock_delay: Option<i64>,
     ) -> Result<()> {
-        instructions::create::handler(ctx, name, symbol, uri)
+        instructions::create::handler(ctx, name, symbol, uri, curve_kind, curve_params, lp_unlock_delay)
     }
 
     /// Buy tokens from the bonding curve
@@ -44,6 +50,18 @@ pub mod pump_fun_clone {
         instructions::buy::handler(ctx, sol_in, min_tokens_out)
     }
 
+    /// Buy an exact amount of tokens from the bonding curve
+    /// 
+    /// Charges the smallest SOL amount (fee included) that buys `tokens_out`,
+    /// failing if it exceeds `max_sol_in`.
+    pub fn buy_exact_tokens(
+        ctx: Context<Buy>,
+        tokens_out: u64,
+        max_sol_in: u64,
+    ) -> Result<()> {
+        instructions::buy_exact_tokens::handler(ctx, tokens_out, max_sol_in)
+    }
+
     /// Sell tokens back to the bonding curve
     /// 
     /// Exchanges tokens for SOL using the constant product bonding curve formula.
@@ -55,16 +73,173 @@ pub mod pump_fun_clone {
         instructions::sell::handler(ctx, tokens_in, min_sol_out)
     }
 
-    /// Complete/graduate the bonding curve to a DEX pool
+    /// Sell tokens to the bonding curve for an exact amount of SOL
+    /// 
+    /// Sells the smallest token amount that pays the seller `sol_out` after
+    /// fees, failing if it exceeds `max_tokens_in`.
+    pub fn sell_for_exact_sol(
+        ctx: Context<Sell>,
+        sol_out: u64,
+        max_tokens_in: u64,
+    ) -> Result<()> {
+        instructions::sell_for_exact_sol::handler(ctx, sol_out, max_tokens_in)
+    }
+
+    /// Complete/graduate the bonding curve into its pool
     /// 
-    /// Marks the curve as complete when threshold is reached. In production,
-    /// this would trigger DEX pool creation.
-    pub fn complete(ctx: Context<Complete>) -> Result<()> {
+    /// Once the curve has graduated, burns the unsold tokens, takes the
+    /// migration fee (part of it rewarding the caller) and moves the rest of
+    /// its real SOL reserve and its liquidity tokens into
+    /// `GlobalConfig.migration_target`: a new constant-product `Pool`, or an
+    /// external AMM through its adapter. The initial LP supply is locked
+    /// either way.
+    pub fn complete<'info>(ctx: Context<'_, '_, 'info, 'info, Complete<'info>>) -> Result<()> {
         instructions::complete::handler(ctx)
     }
+
+    /// Move a graduated curve's liquidity to the configured migration wallet
+    /// 
+    /// Authority-only, and only while the migration target is
+    /// `MigrationTarget::Wallet`. Burns unsold tokens, then sends the whole
+    /// real SOL reserve and the liquidity tokens to the wallet; no migration
+    /// fee is taken.
+    pub fn withdraw_for_migration(ctx: Context<WithdrawForMigration>) -> Result<()> {
+        instructions::withdraw_for_migration::handler(ctx)
+    }
+
+    /// Close a migrated bonding curve and return its rent to the creator
+    /// 
+    /// Callable by the creator or the protocol authority once the liquidity
+    /// has migrated. Closes the bonding curve, its vault and its token
+    /// account, and emits their final state as `CurveClosed`.
+    pub fn close_curve(ctx: Context<CloseCurve>) -> Result<()> {
+        instructions::close_curve::handler(ctx)
+    }
+
+    /// Swap SOL and tokens on a graduated curve's pool
+    /// 
+    /// Exact input in the given direction, failing if the output is below
+    /// `min_amount_out`. The pool fee stays in the pool for LP holders.
+    pub fn swap(
+        ctx: Context<Swap>,
+        direction: SwapDirection,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::swap::handler(ctx, direction, amount_in, min_amount_out)
+    }
+
+    /// Deposit SOL and tokens into a pool for LP tokens
+    /// 
+    /// Tokens are taken in proportion to `sol_amount` at the pool's reserve
+    /// ratio, failing if they exceed `max_tokens_in` or the LP minted is below
+    /// `min_lp_out`.
+    pub fn add_liquidity(
+        ctx: Context<Liquidity>,
+        sol_amount: u64,
+        max_tokens_in: u64,
+        min_lp_out: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity::handler(ctx, sol_amount, max_tokens_in, min_lp_out)
+    }
+
+    /// Burn LP tokens for a proportional share of a pool's reserves
+    pub fn remove_liquidity(
+        ctx: Context<Liquidity>,
+        lp_amount: u64,
+        min_sol_out: u64,
+        min_tokens_out: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity::handler(ctx, lp_amount, min_sol_out, min_tokens_out)
+    }
+
+    /// Release a pool's locked LP tokens to the creator
+    /// 
+    /// Only after the unlock delay chosen at `create`; permanent locks never
+    /// release.
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+        instructions::unlock_liquidity::handler(ctx)
+    }
+
+    /// Update protocol parameters
+    /// 
+    /// Authority-only. Any parameter left as `None` keeps its current value.
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        params: UpdateConfigParams,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    /// Upgrade a global config created by the original program
+    /// 
+    /// Authority-only and one-time. Reallocates the account to the current
+    /// `GlobalConfig` size and sets the newer fields to their defaults.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
+    }
+
+    /// Propose a new protocol authority
+    /// 
+    /// First step of a two-step transfer. Control only moves once the
+    /// proposed authority calls `accept_authority`.
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// Accept a pending authority transfer
+    /// 
+    /// Must be signed by the proposed authority.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Cancel a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::cancel_authority_transfer::handler(ctx)
+    }
+
+    /// Pause or resume the whole protocol
+    /// 
+    /// While paused, create, buy, sell and complete are rejected.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    /// Halt or resume trading on a single bonding curve
+    pub fn set_trading_halted(ctx: Context<SetTradingHalted>, halted: bool) -> Result<()> {
+        instructions::set_trading_halted::handler(ctx, halted)
+    }
+
+    /// Quote a buy of `sol_in` lamports without executing it
+    /// 
+    /// Read-only. Returns a Borsh-encoded `BuyQuote` via return data, for use
+    /// with `simulateTransaction` or CPI.
+    pub fn quote_buy(ctx: Context<Quote>, sol_in: u64) -> Result<BuyQuote> {
+        instructions::quote_buy::handler(ctx, sol_in)
+    }
+
+    /// Quote a sell of `tokens_in` tokens without executing it
+    /// 
+    /// Read-only. Returns a Borsh-encoded `SellQuote` via return data.
+    pub fn quote_sell(ctx: Context<Quote>, tokens_in: u64) -> Result<SellQuote> {
+        instructions::quote_sell::handler(ctx, tokens_in)
+    }
+
+    /// Quote the current spot price and reserves of a bonding curve
+    /// 
+    /// Read-only. Returns a Borsh-encoded `PriceQuote` via return data.
+    pub fn quote_price(ctx: Context<Quote>) -> Result<PriceQuote> {
+        instructions::quote_price::handler(ctx)
+    }
 }
 
 // Re-export for external use
 pub use state::*;
 pub use errors::*;
 pub use constants::*;
+pub use curve::*;
+pub use migration::*;
@@ -0,0 +1,386 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::PumpFunError;
+use crate::instructions::complete::{Complete, CompleteBumps, LiquidityMigrated, PoolCreated};
+
+/// Venue `complete` moves a graduated curve's liquidity into, chosen in
+/// `GlobalConfig`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrationTarget {
+    /// The program's own constant-product `Pool`
+    #[default]
+    Pool,
+    /// External constant-product AMM deployed at `program_id`, reached by CPI
+    /// through the `Cpmm` adapter
+    Cpmm { program_id: Pubkey },
+    /// Authority-held escrow: `complete` is disabled and the authority moves
+    /// the liquidity to `wallet` with `withdraw_for_migration`, for off-chain
+    /// migration tooling
+    Wallet { wallet: Pubkey },
+}
+
+impl MigrationTarget {
+    /// Serialized size of the largest variant
+    pub const SIZE: usize = 1 + // variant tag
+        32; // program_id / wallet
+
+    /// External venues must be a real program other than this one, and the
+    /// migration wallet must be set
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            MigrationTarget::Pool => {}
+            MigrationTarget::Cpmm { program_id } => require!(
+                program_id != Pubkey::default() && program_id != crate::ID,
+                PumpFunError::InvalidMigrationTarget
+            ),
+            MigrationTarget::Wallet { wallet } => require!(
+                wallet != Pubkey::default(),
+                PumpFunError::InvalidMigrationTarget
+            ),
+        }
+        Ok(())
+    }
+
+    /// Adapter moving liquidity into this venue
+    ///
+    /// `Wallet` has none, since its liquidity leaves through
+    /// `withdraw_for_migration` rather than `complete`.
+    pub fn adapter(&self) -> Result<Box<dyn DexAdapter>> {
+        match *self {
+            MigrationTarget::Pool => Ok(Box::new(InProgramPool)),
+            MigrationTarget::Cpmm { program_id } => Ok(Box::new(Cpmm { program_id })),
+            MigrationTarget::Wallet { .. } => err!(PumpFunError::InvalidMigrationTarget),
+        }
+    }
+}
+
+/// Liquidity `complete` hands to the adapter
+pub struct Migration {
+    /// The curve's real SOL reserve net of the migration fee, still in the
+    /// curve vault
+    pub sol_amount: u64,
+    /// Liquidity tokens, still in the curve's token account
+    pub token_amount: u64,
+    /// Unsold allocated tokens burned before migrating
+    pub tokens_burned: u64,
+}
+
+/// Where the migrated liquidity ended up
+pub struct MigrationReceipt {
+    /// Venue pool holding the liquidity
+    pub pool: Pubkey,
+    /// Mint of the LP tokens received for it
+    pub lp_mint: Pubkey,
+    /// LP tokens received, held by the curve's `LpLock`
+    pub lp_amount: u64,
+}
+
+/// Common interface implemented by every migration venue
+///
+/// An adapter moves the whole of `migration.sol_amount` out of the curve
+/// vault and `migration.token_amount` out of the curve's token account, and
+/// has the LP tokens minted into the `LpLock`'s Token-2022 ATA so that
+/// `unlock_liquidity` can release them. Venue-specific accounts come from
+/// the `complete` remaining accounts. Supporting another venue (Raydium
+/// CPMM, Meteora, Orca Whirlpool, ...) means adding a `MigrationTarget`
+/// variant and an adapter for its interface.
+pub trait DexAdapter {
+    fn migrate<'info>(
+        &self,
+        accounts: &mut Complete<'info>,
+        bumps: &CompleteBumps,
+        venue_accounts: &[AccountInfo<'info>],
+        migration: &Migration,
+        clock: &Clock,
+    ) -> Result<MigrationReceipt>;
+}
+
+/// Seeds the program's own `Pool` and mints its initial LP supply
+///
+/// Uses the optional pool accounts of `complete`, which must all be present.
+pub struct InProgramPool;
+
+impl DexAdapter for InProgramPool {
+    fn migrate<'info>(
+        &self,
+        accounts: &mut Complete<'info>,
+        bumps: &CompleteBumps,
+        venue_accounts: &[AccountInfo<'info>],
+        migration: &Migration,
+        clock: &Clock,
+    ) -> Result<MigrationReceipt> {
+        require!(
+            venue_accounts.is_empty() && accounts.migration_escrow.is_none(),
+            PumpFunError::InvalidMigrationAccounts
+        );
+        let (
+            Some(pool),
+            Some(pool_bump),
+            Some(lp_mint),
+            Some(pool_token_account),
+            Some(lock_lp_account),
+        ) = (
+            accounts.pool.as_mut(),
+            bumps.pool,
+            accounts.lp_mint.as_ref(),
+            accounts.pool_token_account.as_ref(),
+            accounts.lock_lp_account.as_ref(),
+        ) else {
+            return err!(PumpFunError::InvalidMigrationAccounts);
+        };
+
+        let mint_key = accounts.bonding_curve.mint;
+        let curve_seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[accounts.bonding_curve.bump],
+        ];
+        let curve_signer = &[&curve_seeds[..]];
+
+        // Move the liquidity tokens to the pool
+        let cpi_accounts = token_2022::TransferChecked {
+            from: accounts.curve_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: pool_token_account.to_account_info(),
+            authority: accounts.bonding_curve.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            curve_signer,
+        );
+        token_2022::transfer_checked(cpi_ctx, migration.token_amount, accounts.mint.decimals)?;
+
+        // Seed the pool and mint the initial LP supply into the lock
+        let lp_supply = pool.initialize(
+            mint_key,
+            lp_mint.key(),
+            migration.sol_amount,
+            migration.token_amount,
+            accounts.global_config.pool_fee_bps,
+            pool_bump,
+            clock,
+        )?;
+
+        let pool_seeds = &[
+            b"pool",
+            mint_key.as_ref(),
+            &[pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: lp_mint.to_account_info(),
+            to: lock_lp_account.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            pool_signer,
+        );
+        token_2022::mint_to(cpi_ctx, lp_supply)?;
+
+        // Move the real SOL reserve to the pool
+        // Both accounts are program-owned, so lamports can be moved directly.
+        // This comes after the CPIs: the runtime rejects a CPI that sees the
+        // pool credited but not the vault debited
+        **accounts.curve_vault.to_account_info().try_borrow_mut_lamports()? -= migration.sol_amount;
+        **pool.to_account_info().try_borrow_mut_lamports()? += migration.sol_amount;
+        pool.assert_solvent(&pool.to_account_info())?;
+
+        emit!(PoolCreated {
+            mint: mint_key,
+            pool: pool.key(),
+            lp_mint: pool.lp_mint,
+            sol_reserve: pool.sol_reserve,
+            token_reserve: pool.token_reserve,
+            lp_supply,
+            tokens_burned: migration.tokens_burned,
+            fee_bps: pool.fee_bps,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(MigrationReceipt {
+            pool: pool.key(),
+            lp_mint: pool.lp_mint,
+            lp_amount: lp_supply,
+        })
+    }
+}
+
+/// Deposits into an external constant-product AMM by CPI
+///
+/// The venue program must expose an Anchor-style `initialize_pool(sol_amount:
+/// u64, token_amount: u64)` instruction taking, in order:
+/// 0. payer (writable, signer): the completer, pays the venue's rent
+/// 1. depositor (signer): the bonding curve, authority of the token source
+/// 2. sol_source (writable, signer): the migration escrow, a system-owned PDA
+///    holding exactly `sol_amount` lamports for the venue to transfer out
+/// 3. mint
+/// 4. token_source (writable): the curve's token account
+/// 5. lp_owner: the `LpLock`, owner of the LP tokens
+/// 6. pool (writable)
+/// 7. token_vault (writable)
+/// 8. lp_mint (writable)
+/// 9. lp_account (writable): `lp_owner`'s Token-2022 ATA of `lp_mint`
+/// 10. token_program, associated_token_program, system_program
+/// 13. curve_vault (writable): trailing account the venue ignores, passed so
+///     that the runtime sees the vault debited along with the escrow credit
+///
+/// `complete` passes accounts 6-9, preceded by the venue program, as its
+/// remaining accounts. The adapter does not trust the venue's bookkeeping:
+/// after the CPI it checks that exactly the migrated amounts left the escrow
+/// and the token source, and that LP tokens reached the lock.
+pub struct Cpmm {
+    pub program_id: Pubkey,
+}
+
+impl DexAdapter for Cpmm {
+    fn migrate<'info>(
+        &self,
+        accounts: &mut Complete<'info>,
+        bumps: &CompleteBumps,
+        venue_accounts: &[AccountInfo<'info>],
+        migration: &Migration,
+        clock: &Clock,
+    ) -> Result<MigrationReceipt> {
+        require!(
+            accounts.pool.is_none()
+                && accounts.lp_mint.is_none()
+                && accounts.pool_token_account.is_none()
+                && accounts.lock_lp_account.is_none(),
+            PumpFunError::InvalidMigrationAccounts
+        );
+        let (Some(escrow), Some(escrow_bump)) = (
+            accounts.migration_escrow.as_ref(),
+            bumps.migration_escrow,
+        ) else {
+            return err!(PumpFunError::InvalidMigrationAccounts);
+        };
+        let [venue_program, pool, token_vault, lp_mint, lp_account] = venue_accounts else {
+            return err!(PumpFunError::InvalidMigrationAccounts);
+        };
+        require_keys_eq!(
+            venue_program.key(),
+            self.program_id,
+            PumpFunError::InvalidMigrationTarget
+        );
+        // LP tokens must land where `unlock_liquidity` looks for them
+        let lp_lock_key = accounts.lp_lock.key();
+        require_keys_eq!(
+            lp_account.key(),
+            get_associated_token_address_with_program_id(
+                &lp_lock_key,
+                &lp_mint.key(),
+                &token_2022::ID
+            ),
+            PumpFunError::InvalidMigrationAccounts
+        );
+
+        let mint_key = accounts.bonding_curve.mint;
+        let curve_seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[accounts.bonding_curve.bump],
+        ];
+        let escrow_seeds = &[
+            b"migration_escrow",
+            mint_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // The vault carries account data, so the venue cannot debit it with a
+        // system transfer; the SOL reserve goes through the escrow instead
+        let escrow_lamports = escrow.lamports();
+        // Reload so the balance reflects the unsold tokens `complete` burned
+        accounts.curve_token_account.reload()?;
+        let curve_tokens = accounts.curve_token_account.amount;
+        **accounts.curve_vault.to_account_info().try_borrow_mut_lamports()? -= migration.sol_amount;
+        **escrow.to_account_info().try_borrow_mut_lamports()? += migration.sol_amount;
+
+        let mut data = hash(b"global:initialize_pool").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&migration.sol_amount.to_le_bytes());
+        data.extend_from_slice(&migration.token_amount.to_le_bytes());
+
+        let ix = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(accounts.completer.key(), true),
+                AccountMeta::new_readonly(accounts.bonding_curve.key(), true),
+                AccountMeta::new(escrow.key(), true),
+                AccountMeta::new_readonly(accounts.mint.key(), false),
+                AccountMeta::new(accounts.curve_token_account.key(), false),
+                AccountMeta::new_readonly(lp_lock_key, false),
+                AccountMeta::new(pool.key(), false),
+                AccountMeta::new(token_vault.key(), false),
+                AccountMeta::new(lp_mint.key(), false),
+                AccountMeta::new(lp_account.key(), false),
+                AccountMeta::new_readonly(accounts.token_program.key(), false),
+                AccountMeta::new_readonly(accounts.associated_token_program.key(), false),
+                AccountMeta::new_readonly(accounts.system_program.key(), false),
+                AccountMeta::new(accounts.curve_vault.key(), false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &[
+                accounts.completer.to_account_info(),
+                accounts.bonding_curve.to_account_info(),
+                escrow.to_account_info(),
+                accounts.mint.to_account_info(),
+                accounts.curve_token_account.to_account_info(),
+                accounts.lp_lock.to_account_info(),
+                pool.clone(),
+                token_vault.clone(),
+                lp_mint.clone(),
+                lp_account.clone(),
+                accounts.token_program.to_account_info(),
+                accounts.associated_token_program.to_account_info(),
+                accounts.system_program.to_account_info(),
+                accounts.curve_vault.to_account_info(),
+                venue_program.clone(),
+            ],
+            &[&curve_seeds[..], &escrow_seeds[..]],
+        )?;
+
+        // Verify the venue took exactly the migrated liquidity
+        accounts.curve_token_account.reload()?;
+        require!(
+            accounts.curve_token_account.amount == curve_tokens - migration.token_amount
+                && escrow.lamports() == escrow_lamports,
+            PumpFunError::MigrationIncomplete
+        );
+        require_keys_eq!(*lp_account.owner, token_2022::ID, PumpFunError::MigrationIncomplete);
+        let lp_balance = TokenAccount::try_deserialize(&mut &lp_account.try_borrow_data()?[..])?;
+        require!(
+            lp_balance.owner == lp_lock_key && lp_balance.mint == lp_mint.key() && lp_balance.amount > 0,
+            PumpFunError::MigrationIncomplete
+        );
+
+        emit!(LiquidityMigrated {
+            mint: mint_key,
+            program_id: self.program_id,
+            pool: pool.key(),
+            lp_mint: lp_mint.key(),
+            sol_amount: migration.sol_amount,
+            token_amount: migration.token_amount,
+            lp_amount: lp_balance.amount,
+            tokens_burned: migration.tokens_burned,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(MigrationReceipt {
+            pool: pool.key(),
+            lp_mint: lp_mint.key(),
+            lp_amount: lp_balance.amount,
+        })
+    }
+}
@@ -1,15 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
-use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
-use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensionsMut;
-use anchor_spl::token_2022::spl_token_2022::state::Mint;
-use anchor_spl::token_2022::spl_token_metadata_interface::instruction::{
-    CreateMetadataAccountV3, CreateMetadataAccountV3InstructionArgs,
-};
-use anchor_spl::token_2022::spl_token_metadata_interface::state::TokenMetadata;
-use anchor_spl::token_2022::{Token2022, TokenAccount, Mint as TokenMint};
-use anchor_spl::token::{self, Mint, TokenAccount as TokenAccountOld};
 use crate::constants::*;
+use crate::curve::{CurveKind, CurvePricing};
+use crate::migration::MigrationTarget;
+use crate::errors::PumpFunError;
 
 /// Global configuration account storing protocol-wide settings
 #[account]
@@ -27,6 +20,40 @@ pub struct GlobalConfig {
     pub total_tokens_created: u64,
     /// Bump seed for treasury PDA
     pub treasury_bump: u8,
+    /// Proposed new authority awaiting acceptance (two-step transfer)
+    pub pending_authority: Option<Pubkey>,
+    /// Protocol-wide emergency pause (halts create, trading and completion)
+    pub paused: bool,
+    /// Maximum token name length in bytes
+    pub max_name_len: u16,
+    /// Maximum token symbol length in bytes
+    pub max_symbol_len: u16,
+    /// Maximum metadata URI length in bytes
+    pub max_uri_len: u16,
+    /// Total trading fees paid to the treasury across all curves, in lamports
+    pub fees_collected: u64,
+    /// Bitmask of `CurveKind`s allowed at `create` (`CURVE_KIND_*`)
+    pub enabled_curve_kinds: u8,
+    /// Lowest start price allowed for linear/exponential curves
+    pub min_start_price: u64,
+    /// Highest end price allowed for linear/exponential curves
+    pub max_end_price: u64,
+    /// Lower bounds for per-curve parameters chosen at `create`
+    pub min_curve_params: CurveParams,
+    /// Upper bounds for per-curve parameters chosen at `create`
+    pub max_curve_params: CurveParams,
+    /// Graduation criterion applied to curves created from now on
+    pub graduation_criterion: GraduationCriterion,
+    /// Swap fee of pools created at graduation from now on, in basis points
+    pub pool_fee_bps: u16,
+    /// Shortest LP lock, in seconds, a creator can choose at `create`
+    pub min_lp_lock_duration: i64,
+    /// Venue `complete` moves graduated liquidity into
+    pub migration_target: MigrationTarget,
+    /// Fee taken from a curve's real SOL reserve at `complete`, in lamports
+    pub migration_fee: u64,
+    /// Part of the migration fee paid to the caller of `complete`, in lamports
+    pub completer_reward: u64,
 }
 
 impl GlobalConfig {
@@ -36,7 +63,24 @@ impl GlobalConfig {
         2 +  // protocol_fee_bps
         8 +  // creation_fee
         8 +  // total_tokens_created
-        1;   // treasury_bump
+        1 +  // treasury_bump
+        33 + // pending_authority (Option<Pubkey>)
+        1 +  // paused
+        2 +  // max_name_len
+        2 +  // max_symbol_len
+        2 +  // max_uri_len
+        8 +  // fees_collected
+        1 +  // enabled_curve_kinds
+        8 +  // min_start_price
+        8 +  // max_end_price
+        CurveParams::SIZE + // min_curve_params
+        CurveParams::SIZE + // max_curve_params
+        1 +  // graduation_criterion
+        2 +  // pool_fee_bps
+        8 +  // min_lp_lock_duration
+        MigrationTarget::SIZE + // migration_target
+        8 +  // migration_fee
+        8;   // completer_reward
 
     pub fn initialize(
         &mut self,
@@ -50,9 +94,398 @@ impl GlobalConfig {
         self.creation_fee = CREATION_FEE;
         self.total_tokens_created = 0;
         self.treasury_bump = treasury_bump;
+        self.pending_authority = None;
+        self.paused = false;
+        self.max_name_len = MAX_NAME_LEN;
+        self.max_symbol_len = MAX_SYMBOL_LEN;
+        self.max_uri_len = MAX_URI_LEN;
+        self.fees_collected = 0;
+        self.enabled_curve_kinds = ALL_CURVE_KINDS;
+        self.min_start_price = DEFAULT_MIN_START_PRICE;
+        self.max_end_price = DEFAULT_MAX_END_PRICE;
+        self.min_curve_params = DEFAULT_MIN_CURVE_PARAMS;
+        self.max_curve_params = DEFAULT_MAX_CURVE_PARAMS;
+        self.graduation_criterion = GraduationCriterion::RealSolRaised;
+        self.pool_fee_bps = DEFAULT_POOL_FEE_BPS;
+        self.min_lp_lock_duration = DEFAULT_MIN_LP_LOCK_DURATION;
+        self.migration_target = MigrationTarget::Pool;
+        self.migration_fee = DEFAULT_MIGRATION_FEE;
+        self.completer_reward = DEFAULT_COMPLETER_REWARD;
+    }
+
+    /// Record a trading fee paid to the treasury
+    pub fn record_fee(&mut self, fee: u64) -> Result<()> {
+        self.fees_collected = self.fees_collected
+            .checked_add(fee)
+            .ok_or(PumpFunError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Migration fee charged on `real_sol_reserve` at `complete`, and the
+    /// completer's part of it, as `(fee, reward)`
+    ///
+    /// Never more than the curve holds; the treasury gets `fee - reward`.
+    pub fn migration_fee_split(&self, real_sol_reserve: u64) -> (u64, u64) {
+        let fee = self.migration_fee.min(real_sol_reserve);
+        (fee, self.completer_reward.min(fee))
+    }
+
+    /// Upgrade a config written before the account grew
+    ///
+    /// Keeps the legacy fields and gives every newer field the value
+    /// `initialize` would.
+    pub fn from_legacy(legacy: &LegacyGlobalConfig) -> Self {
+        let mut config = Self::default();
+        config.initialize(legacy.authority, legacy.treasury, legacy.treasury_bump);
+        config.protocol_fee_bps = legacy.protocol_fee_bps;
+        config.creation_fee = legacy.creation_fee;
+        config.total_tokens_created = legacy.total_tokens_created;
+        config
+    }
+
+    /// Snapshot of the tunable protocol parameters
+    pub fn params(&self) -> ConfigParams {
+        ConfigParams {
+            protocol_fee_bps: self.protocol_fee_bps,
+            creation_fee: self.creation_fee,
+            max_name_len: self.max_name_len,
+            max_symbol_len: self.max_symbol_len,
+            max_uri_len: self.max_uri_len,
+            enabled_curve_kinds: self.enabled_curve_kinds,
+            min_start_price: self.min_start_price,
+            max_end_price: self.max_end_price,
+            min_curve_params: self.min_curve_params,
+            max_curve_params: self.max_curve_params,
+            graduation_criterion: self.graduation_criterion,
+            pool_fee_bps: self.pool_fee_bps,
+            min_lp_lock_duration: self.min_lp_lock_duration,
+            migration_target: self.migration_target,
+            migration_fee: self.migration_fee,
+            completer_reward: self.completer_reward,
+        }
+    }
+
+    /// Overwrite the tunable protocol parameters after validating bounds
+    pub fn set_params(&mut self, params: &ConfigParams) -> Result<()> {
+        params.validate()?;
+        self.protocol_fee_bps = params.protocol_fee_bps;
+        self.creation_fee = params.creation_fee;
+        self.max_name_len = params.max_name_len;
+        self.max_symbol_len = params.max_symbol_len;
+        self.max_uri_len = params.max_uri_len;
+        self.enabled_curve_kinds = params.enabled_curve_kinds;
+        self.min_start_price = params.min_start_price;
+        self.max_end_price = params.max_end_price;
+        self.min_curve_params = params.min_curve_params;
+        self.max_curve_params = params.max_curve_params;
+        self.graduation_criterion = params.graduation_criterion;
+        self.pool_fee_bps = params.pool_fee_bps;
+        self.min_lp_lock_duration = params.min_lp_lock_duration;
+        self.migration_target = params.migration_target;
+        self.migration_fee = params.migration_fee;
+        self.completer_reward = params.completer_reward;
+        Ok(())
+    }
+
+    /// Check a requested curve kind against the authority-approved bounds
+    ///
+    /// The kind must be enabled, and stepped curves must start at or above
+    /// `min_start_price`, never decrease, and end at or below `max_end_price`.
+    pub fn validate_curve_kind(&self, curve_kind: &CurveKind) -> Result<()> {
+        require!(
+            self.enabled_curve_kinds & curve_kind.bit() != 0,
+            PumpFunError::CurveKindDisabled
+        );
+
+        let start_price = match *curve_kind {
+            CurveKind::ConstantProduct => return Ok(()),
+            CurveKind::Linear { start_price, .. } => start_price,
+            CurveKind::Exponential { start_price, .. } => start_price,
+        };
+        let end_price = curve_kind
+            .end_price()
+            .map_err(|_| PumpFunError::InvalidCurveParams)?
+            .ok_or(PumpFunError::InvalidCurveParams)?;
+
+        require!(
+            start_price >= self.min_start_price
+                && end_price >= start_price
+                && end_price <= self.max_end_price,
+            PumpFunError::InvalidCurveParams
+        );
+
+        Ok(())
+    }
+
+    /// Check requested curve parameters against the authority-approved ranges
+    ///
+    /// Every field must lie within `min_curve_params..=max_curve_params`, and
+    /// the set must be consistent (see `CurveParams::is_consistent`). Under
+    /// `TokensSold`, a constant-product curve needs a virtual token reserve
+    /// above its allocation, or it could never sell out.
+    pub fn validate_curve_params(
+        &self,
+        curve_kind: &CurveKind,
+        curve_params: &CurveParams,
+    ) -> Result<()> {
+        require!(
+            curve_params.is_within(&self.min_curve_params, &self.max_curve_params)
+                && curve_params.is_consistent(),
+            PumpFunError::InvalidCurveParams
+        );
+        if self.graduation_criterion == GraduationCriterion::TokensSold
+            && *curve_kind == CurveKind::ConstantProduct
+        {
+            require!(
+                curve_params.initial_virtual_token_reserve > curve_params.token_allocation,
+                PumpFunError::InvalidCurveParams
+            );
+        }
+        Ok(())
+    }
+
+    /// Check a creator's LP unlock delay against the configured minimum
+    ///
+    /// `None` locks the pool's initial LP supply permanently and is always
+    /// allowed.
+    pub fn validate_lp_unlock_delay(&self, lp_unlock_delay: Option<i64>) -> Result<()> {
+        if let Some(delay) = lp_unlock_delay {
+            require!(
+                delay >= self.min_lp_lock_duration,
+                PumpFunError::InvalidLpLock
+            );
+        }
+        Ok(())
+    }
+
+    /// Validate token metadata against the configured limits
+    ///
+    /// Rejects empty strings, control characters, values over the configured
+    /// byte lengths and URIs outside `ALLOWED_URI_SCHEMES`.
+    pub fn validate_metadata(&self, name: &str, symbol: &str, uri: &str) -> Result<()> {
+        for value in [name, symbol, uri] {
+            require!(
+                !value.is_empty() && !value.chars().any(char::is_control),
+                PumpFunError::InvalidMetadata
+            );
+        }
+
+        require!(
+            name.len() <= self.max_name_len as usize,
+            PumpFunError::NameTooLong
+        );
+        require!(
+            symbol.len() <= self.max_symbol_len as usize,
+            PumpFunError::SymbolTooLong
+        );
+        require!(
+            uri.len() <= self.max_uri_len as usize,
+            PumpFunError::UriTooLong
+        );
+        require!(
+            ALLOWED_URI_SCHEMES.iter().any(|scheme| {
+                uri.len() > scheme.len() && uri.starts_with(scheme)
+            }),
+            PumpFunError::UnsupportedUriScheme
+        );
+
+        Ok(())
+    }
+}
+
+/// `GlobalConfig` as first deployed, before the protocol gained its newer
+/// settings
+///
+/// Shares `GlobalConfig`'s discriminator and is a prefix of its layout.
+/// `migrate_config` reads it to upgrade a config account created by the
+/// original program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyGlobalConfig {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub creation_fee: u64,
+    pub total_tokens_created: u64,
+    pub treasury_bump: u8,
+}
+
+impl LegacyGlobalConfig {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // treasury
+        2 +  // protocol_fee_bps
+        8 +  // creation_fee
+        8 +  // total_tokens_created
+        1;   // treasury_bump
+}
+
+/// Tunable protocol parameters stored in `GlobalConfig`
+///
+/// Used both to apply updates and to record old/new values in events.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigParams {
+    /// Protocol fee in basis points
+    pub protocol_fee_bps: u16,
+    /// Token creation fee in lamports
+    pub creation_fee: u64,
+    /// Maximum token name length in bytes
+    pub max_name_len: u16,
+    /// Maximum token symbol length in bytes
+    pub max_symbol_len: u16,
+    /// Maximum metadata URI length in bytes
+    pub max_uri_len: u16,
+    /// Bitmask of curve kinds allowed at `create`
+    pub enabled_curve_kinds: u8,
+    /// Lowest start price for linear/exponential curves
+    pub min_start_price: u64,
+    /// Highest end price for linear/exponential curves
+    pub max_end_price: u64,
+    /// Lower bounds for per-curve parameters
+    pub min_curve_params: CurveParams,
+    /// Upper bounds for per-curve parameters
+    pub max_curve_params: CurveParams,
+    /// Graduation criterion for new curves
+    pub graduation_criterion: GraduationCriterion,
+    /// Swap fee of new graduation pools in basis points
+    pub pool_fee_bps: u16,
+    /// Shortest LP lock in seconds for new curves
+    pub min_lp_lock_duration: i64,
+    /// Venue graduated liquidity migrates into
+    pub migration_target: MigrationTarget,
+    /// Fee taken from the real SOL reserve at `complete` in lamports
+    pub migration_fee: u64,
+    /// Part of the migration fee paid to the completer in lamports
+    pub completer_reward: u64,
+}
+
+impl ConfigParams {
+    /// Check every parameter against its protocol bounds
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            PumpFunError::InvalidProtocolFee
+        );
+        require!(
+            self.creation_fee <= MAX_CREATION_FEE,
+            PumpFunError::InvalidCreationFee
+        );
+        require!(
+            (1..=NAME_LEN_LIMIT).contains(&self.max_name_len)
+                && (1..=SYMBOL_LEN_LIMIT).contains(&self.max_symbol_len)
+                && (1..=URI_LEN_LIMIT).contains(&self.max_uri_len),
+            PumpFunError::InvalidMetadataLimit
+        );
+        require!(
+            self.enabled_curve_kinds != 0
+                && self.enabled_curve_kinds & !ALL_CURVE_KINDS == 0
+                && self.min_start_price > 0
+                && self.min_start_price <= self.max_end_price,
+            PumpFunError::InvalidCurveBounds
+        );
+        // min_curve_params <= max_curve_params field by field
+        require!(
+            self.min_curve_params.is_within(&self.min_curve_params, &self.max_curve_params)
+                && self.min_curve_params.initial_virtual_sol_reserve > 0
+                && self.min_curve_params.initial_virtual_token_reserve > 0
+                && self.min_curve_params.graduation_threshold > 0
+                && self.min_curve_params.token_allocation >= CURVE_STEPS
+                && self.max_curve_params.token_allocation <= TOTAL_SUPPLY,
+            PumpFunError::InvalidCurveBounds
+        );
+        require!(
+            self.pool_fee_bps <= MAX_POOL_FEE_BPS,
+            PumpFunError::InvalidPoolFee
+        );
+        require!(
+            self.min_lp_lock_duration >= 0,
+            PumpFunError::InvalidLpLock
+        );
+        self.migration_target.validate()?;
+        require!(
+            self.completer_reward <= self.migration_fee
+                && self.migration_fee <= MAX_MIGRATION_FEE,
+            PumpFunError::InvalidMigrationFee
+        );
+        // Only under `RealSolRaised` is the threshold the SOL a curve holds at
+        // graduation; there the fee must leave SOL for the pool of a curve
+        // graduating at the lowest threshold
+        if self.graduation_criterion == GraduationCriterion::RealSolRaised {
+            require!(
+                self.migration_fee < self.min_curve_params.graduation_threshold,
+                PumpFunError::InvalidMigrationFee
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Launch parameters of a single bonding curve, chosen at `create`
+///
+/// Lets one program run several launch tiers; each field is bounded by
+/// `GlobalConfig.min_curve_params` / `max_curve_params`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CurveParams {
+    /// Virtual SOL reserve the curve starts with, in lamports
+    pub initial_virtual_sol_reserve: u64,
+    /// Virtual token reserve the curve starts with
+    pub initial_virtual_token_reserve: u64,
+    /// Lamports of real SOL raised or of market cap at which the curve
+    /// graduates, depending on its `GraduationCriterion`
+    pub graduation_threshold: u64,
+    /// Tokens the curve can sell; the rest of the supply is kept for liquidity
+    pub token_allocation: u64,
+}
+
+impl CurveParams {
+    pub const SIZE: usize = 8 + // initial_virtual_sol_reserve
+        8 + // initial_virtual_token_reserve
+        8 + // graduation_threshold
+        8;  // token_allocation
+
+    fn fields(&self) -> [u64; 4] {
+        [
+            self.initial_virtual_sol_reserve,
+            self.initial_virtual_token_reserve,
+            self.graduation_threshold,
+            self.token_allocation,
+        ]
+    }
+
+    /// Whether every field lies within the matching `min..=max` fields
+    pub fn is_within(&self, min: &CurveParams, max: &CurveParams) -> bool {
+        self.fields()
+            .iter()
+            .zip(min.fields().iter().zip(max.fields().iter()))
+            .all(|(value, (min, max))| min <= value && value <= max)
+    }
+
+    /// Whether the parameters describe a usable curve
+    ///
+    /// `token_allocation` must cover every price step, leave part of the
+    /// supply to seed the graduation pool, and fit in the virtual token
+    /// reserve, so the reserve never reaches zero.
+    pub fn is_consistent(&self) -> bool {
+        self.token_allocation >= CURVE_STEPS
+            && self.token_allocation < TOTAL_SUPPLY
+            && self.token_allocation <= self.initial_virtual_token_reserve
     }
 }
 
+/// When a bonding curve graduates
+///
+/// Whatever the criterion, a curve that has sold its whole allocation has
+/// graduated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraduationCriterion {
+    /// `real_sol_reserve` reaches `graduation_threshold`
+    #[default]
+    RealSolRaised,
+    /// `tokens_sold` reaches `token_allocation`
+    TokensSold,
+    /// Spot price * `TOTAL_SUPPLY` reaches `graduation_threshold`
+    MarketCap,
+}
+
 /// Bonding curve account storing state for each token's bonding curve
 #[account]
 pub struct BondingCurve {
@@ -60,13 +493,13 @@ pub struct BondingCurve {
     pub mint: Pubkey,
     /// Creator of the token
     pub creator: Pubkey,
-    /// Virtual SOL reserve (starts at INITIAL_VIRTUAL_SOL_RESERVE)
+    /// Virtual SOL reserve (starts at `initial_virtual_sol_reserve`)
     pub virtual_sol_reserve: u64,
-    /// Virtual token reserve (starts at INITIAL_VIRTUAL_TOKEN_RESERVE)
+    /// Virtual token reserve (starts at `initial_virtual_token_reserve`)
     pub virtual_token_reserve: u64,
-    /// Real SOL accumulated from buys (sent to treasury initially, then used for LP)
+    /// Real SOL held in the curve vault (net of protocol fees), later used for LP
     pub real_sol_reserve: u64,
-    /// Total tokens sold (minted and sold)
+    /// Total tokens sold (transferred out of the curve's token account)
     pub tokens_sold: u64,
     /// Whether the bonding curve has been completed/graduated
     pub completed: bool,
@@ -76,6 +509,32 @@ pub struct BondingCurve {
     pub completed_at: Option<i64>,
     /// Bump seed for this bonding curve PDA
     pub bump: u8,
+    /// Per-curve trading halt set by the protocol authority
+    pub trading_halted: bool,
+    /// Total trading fees this curve paid to the treasury, in lamports
+    pub fees_collected: u64,
+    /// Pricing model chosen at `create`
+    pub curve_kind: CurveKind,
+    /// Virtual SOL reserve the curve started with
+    pub initial_virtual_sol_reserve: u64,
+    /// Virtual token reserve the curve started with
+    pub initial_virtual_token_reserve: u64,
+    /// Real SOL raised or market cap at which the curve graduates, in lamports
+    pub graduation_threshold: u64,
+    /// Tokens this curve can sell
+    pub token_allocation: u64,
+    /// Graduation criterion in force when the curve was created
+    pub graduation_criterion: GraduationCriterion,
+    /// Whether the curve's liquidity has left it, through `complete` or
+    /// `withdraw_for_migration`
+    pub migrated: bool,
+    /// Seconds after graduation before the creator can unlock the pool's
+    /// initial LP supply, or `None` to lock it permanently
+    pub lp_unlock_delay: Option<i64>,
+    /// SOL moved out of the curve vault at migration, net of the migration fee
+    pub migrated_sol: u64,
+    /// Liquidity tokens moved out of the curve's token account at migration
+    pub migrated_tokens: u64,
 }
 
 impl BondingCurve {
@@ -89,57 +548,227 @@ impl BondingCurve {
         1 +  // completed
         8 +  // created_at
         9 +  // completed_at (Option<i64>)
-        1;   // bump
+        1 +  // bump
+        1 +  // trading_halted
+        8 +  // fees_collected
+        CurveKind::SIZE + // curve_kind
+        8 +  // initial_virtual_sol_reserve
+        8 +  // initial_virtual_token_reserve
+        8 +  // graduation_threshold
+        8 +  // token_allocation
+        1 +  // graduation_criterion
+        1 +  // migrated
+        9 +  // lp_unlock_delay (Option<i64>)
+        8 +  // migrated_sol
+        8;   // migrated_tokens
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         mint: Pubkey,
         creator: Pubkey,
+        curve_kind: CurveKind,
+        curve_params: &CurveParams,
+        graduation_criterion: GraduationCriterion,
+        lp_unlock_delay: Option<i64>,
         bump: u8,
         clock: &Clock,
     ) {
         self.mint = mint;
         self.creator = creator;
-        self.virtual_sol_reserve = INITIAL_VIRTUAL_SOL_RESERVE;
-        self.virtual_token_reserve = INITIAL_VIRTUAL_TOKEN_RESERVE;
+        self.virtual_sol_reserve = curve_params.initial_virtual_sol_reserve;
+        self.virtual_token_reserve = curve_params.initial_virtual_token_reserve;
         self.real_sol_reserve = 0;
         self.tokens_sold = 0;
         self.completed = false;
         self.created_at = clock.unix_timestamp;
         self.completed_at = None;
         self.bump = bump;
+        self.trading_halted = false;
+        self.fees_collected = 0;
+        self.curve_kind = curve_kind;
+        self.initial_virtual_sol_reserve = curve_params.initial_virtual_sol_reserve;
+        self.initial_virtual_token_reserve = curve_params.initial_virtual_token_reserve;
+        self.graduation_threshold = curve_params.graduation_threshold;
+        self.token_allocation = curve_params.token_allocation;
+        self.graduation_criterion = graduation_criterion;
+        self.migrated = false;
+        self.lp_unlock_delay = lp_unlock_delay;
+        self.migrated_sol = 0;
+        self.migrated_tokens = 0;
+    }
+
+    /// Parameters this curve was created with
+    pub fn curve_params(&self) -> CurveParams {
+        CurveParams {
+            initial_virtual_sol_reserve: self.initial_virtual_sol_reserve,
+            initial_virtual_token_reserve: self.initial_virtual_token_reserve,
+            graduation_threshold: self.graduation_threshold,
+            token_allocation: self.token_allocation,
+        }
+    }
+
+    /// Pricing for this curve's kind at its current state
+    pub fn pricing(&self) -> Box<dyn CurvePricing> {
+        self.curve_kind.pricing(
+            self.virtual_sol_reserve,
+            self.virtual_token_reserve,
+            self.tokens_sold,
+            self.token_allocation,
+        )
+    }
+
+    /// Whether the curve has met its graduation criterion or sold out
+    pub fn graduation_reached(&self) -> Result<bool> {
+        if self.tokens_sold >= self.token_allocation {
+            return Ok(true);
+        }
+
+        Ok(match self.graduation_criterion {
+            GraduationCriterion::RealSolRaised => {
+                self.real_sol_reserve >= self.graduation_threshold
+            }
+            GraduationCriterion::TokensSold => false,
+            GraduationCriterion::MarketCap => {
+                self.pricing().spot_price()? >= self.graduation_price()?
+            }
+        })
+    }
+
+    /// Spot price at which the market cap reaches `graduation_threshold`
+    /// Formula: price = ceil(graduation_threshold * PRICE_DENOMINATOR / TOTAL_SUPPLY)
+    pub fn graduation_price(&self) -> Result<u64> {
+        to_u64(div_ceil(
+            (self.graduation_threshold as u128)
+                .checked_mul(PRICE_DENOMINATOR)
+                .ok_or(PumpFunError::MathOverflow)?,
+            TOTAL_SUPPLY as u128,
+        )?)
+    }
+
+    /// The buy that takes the curve exactly to graduation, as
+    /// `(sol_to_curve, tokens_out)`, or `None` if no single buy can reach it
+    ///
+    /// Buys at or above this size are capped to it, so the last buyer never
+    /// pays past the threshold.
+    pub fn graduation_cap(&self) -> Result<Option<(u64, u64)>> {
+        let pricing = self.pricing();
+        let remaining = self.token_allocation
+            .checked_sub(self.tokens_sold)
+            .ok_or(PumpFunError::CurveSupplyExceeded)?;
+
+        let criterion_cap = match self.graduation_criterion {
+            GraduationCriterion::RealSolRaised => {
+                let sol = self.graduation_threshold.saturating_sub(self.real_sol_reserve);
+                // Fails if the allocation would run out first
+                pricing.tokens_out(sol).ok().map(|tokens| (sol, tokens))
+            }
+            GraduationCriterion::TokensSold => None,
+            GraduationCriterion::MarketCap => {
+                match pricing.tokens_to_price(self.graduation_price()?)? {
+                    Some(tokens) if tokens > 0 && tokens <= remaining => {
+                        Some((pricing.sol_in(tokens)?, tokens))
+                    }
+                    _ => None,
+                }
+            }
+        };
+
+        match criterion_cap {
+            Some((sol, tokens)) if tokens <= remaining => Ok(Some((sol, tokens))),
+            // Otherwise the curve graduates by selling out, if that is reachable
+            _ => Ok(pricing.sol_in(remaining).ok().map(|sol| (sol, remaining))),
+        }
     }
 
     /// Update reserves after a buy operation
-    pub fn update_after_buy(&mut self, sol_in: u64, tokens_out: u64) {
-        self.virtual_sol_reserve = self.virtual_sol_reserve
+    ///
+    /// `sol_in` is the amount deposited into the curve vault, after the fee.
+    /// Fails without modifying state if any reserve would overflow or underflow,
+    /// or if `tokens_sold` would exceed `token_allocation`.
+    pub fn update_after_buy(&mut self, sol_in: u64, tokens_out: u64) -> Result<()> {
+        let virtual_sol_reserve = self.virtual_sol_reserve
             .checked_add(sol_in)
-            .expect("Math overflow");
-        self.virtual_token_reserve = self.virtual_token_reserve
+            .ok_or(PumpFunError::MathOverflow)?;
+        let virtual_token_reserve = self.virtual_token_reserve
             .checked_sub(tokens_out)
-            .expect("Math overflow");
-        self.real_sol_reserve = self.real_sol_reserve
+            .ok_or(PumpFunError::InsufficientLiquidity)?;
+        let real_sol_reserve = self.real_sol_reserve
             .checked_add(sol_in)
-            .expect("Math overflow");
-        self.tokens_sold = self.tokens_sold
+            .ok_or(PumpFunError::MathOverflow)?;
+        let tokens_sold = self.tokens_sold
             .checked_add(tokens_out)
-            .expect("Math overflow");
+            .ok_or(PumpFunError::MathOverflow)?;
+
+        require!(tokens_sold <= self.token_allocation, PumpFunError::CurveSupplyExceeded);
+
+        self.virtual_sol_reserve = virtual_sol_reserve;
+        self.virtual_token_reserve = virtual_token_reserve;
+        self.real_sol_reserve = real_sol_reserve;
+        self.tokens_sold = tokens_sold;
+        Ok(())
     }
 
     /// Update reserves after a sell operation
-    pub fn update_after_sell(&mut self, tokens_in: u64, sol_out: u64) {
-        self.virtual_sol_reserve = self.virtual_sol_reserve
+    ///
+    /// `sol_out` is the amount withdrawn from the curve vault, before the fee.
+    /// Fails without modifying state if any reserve would overflow or underflow.
+    pub fn update_after_sell(&mut self, tokens_in: u64, sol_out: u64) -> Result<()> {
+        let virtual_sol_reserve = self.virtual_sol_reserve
             .checked_sub(sol_out)
-            .expect("Math overflow");
-        self.virtual_token_reserve = self.virtual_token_reserve
+            .ok_or(PumpFunError::InsufficientLiquidity)?;
+        let virtual_token_reserve = self.virtual_token_reserve
             .checked_add(tokens_in)
-            .expect("Math overflow");
-        self.real_sol_reserve = self.real_sol_reserve
+            .ok_or(PumpFunError::MathOverflow)?;
+        let real_sol_reserve = self.real_sol_reserve
             .checked_sub(sol_out)
-            .expect("Math overflow");
-        self.tokens_sold = self.tokens_sold
+            .ok_or(PumpFunError::InsufficientLiquidity)?;
+        let tokens_sold = self.tokens_sold
             .checked_sub(tokens_in)
-            .expect("Math overflow");
+            .ok_or(PumpFunError::TokensSoldUnderflow)?;
+
+        self.virtual_sol_reserve = virtual_sol_reserve;
+        self.virtual_token_reserve = virtual_token_reserve;
+        self.real_sol_reserve = real_sol_reserve;
+        self.tokens_sold = tokens_sold;
+        Ok(())
+    }
+
+    /// Record a trading fee this curve paid to the treasury
+    pub fn record_fee(&mut self, fee: u64) -> Result<()> {
+        self.fees_collected = self.fees_collected
+            .checked_add(fee)
+            .ok_or(PumpFunError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Check the curve's accounting invariants after a state transition
+    ///
+    /// - `tokens_sold` never exceeds `token_allocation`
+    /// - the curve vault covers `real_sol_reserve` (see `assert_vault_solvent`)
+    pub fn check_invariants(&self, curve_vault: &AccountInfo) -> Result<()> {
+        require!(
+            self.tokens_sold <= self.token_allocation,
+            PumpFunError::CurveSupplyExceeded
+        );
+        self.assert_vault_solvent(curve_vault)
+    }
+
+    /// Check that the curve vault holds at least `real_sol_reserve` lamports
+    /// on top of its own rent-exempt minimum
+    pub fn assert_vault_solvent(&self, curve_vault: &AccountInfo) -> Result<()> {
+        let rent_exempt = Rent::get()?.minimum_balance(curve_vault.data_len());
+        let available = curve_vault
+            .lamports()
+            .checked_sub(rent_exempt)
+            .ok_or(PumpFunError::VaultInsolvent)?;
+
+        require!(
+            available >= self.real_sol_reserve,
+            PumpFunError::VaultInsolvent
+        );
+
+        Ok(())
     }
 
     /// Mark bonding curve as completed
@@ -147,4 +776,318 @@ impl BondingCurve {
         self.completed = true;
         self.completed_at = Some(clock.unix_timestamp);
     }
+
+    /// Tokens kept out of the curve's allocation to seed its pool
+    /// Formula: liquidity_tokens = TOTAL_SUPPLY - token_allocation
+    pub fn liquidity_tokens(&self) -> Result<u64> {
+        TOTAL_SUPPLY
+            .checked_sub(self.token_allocation)
+            .ok_or(PumpFunError::CurveSupplyExceeded.into())
+    }
+
+    /// Allocated tokens the curve never sold, burned at migration
+    pub fn unsold_tokens(&self) -> Result<u64> {
+        self.token_allocation
+            .checked_sub(self.tokens_sold)
+            .ok_or(PumpFunError::CurveSupplyExceeded.into())
+    }
+
+    /// Record that the real SOL reserve has left the curve vault, with the
+    /// SOL and liquidity tokens that reached the migration venue or wallet
+    pub fn record_migration(&mut self, sol_amount: u64, token_amount: u64) {
+        self.real_sol_reserve = 0;
+        self.migrated = true;
+        self.migrated_sol = sol_amount;
+        self.migrated_tokens = token_amount;
+    }
+}
+
+/// Per-curve SOL vault holding the curve's real SOL reserve
+///
+/// Program-owned PDA at `[b"curve_vault", mint]`, so buys and sells of one
+/// curve can never touch SOL belonging to another curve.
+#[account]
+#[derive(Default)]
+pub struct CurveVault {
+    /// Bonding curve this vault belongs to
+    pub bonding_curve: Pubkey,
+    /// Bump seed for this vault PDA
+    pub bump: u8,
+}
+
+impl CurveVault {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // bonding_curve
+        1;   // bump
+}
+
+/// Direction of a pool swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    /// SOL in, tokens out
+    SolToToken,
+    /// Tokens in, SOL out
+    TokenToSol,
+}
+
+/// Constant-product AMM pool a bonding curve graduates into
+///
+/// Program-owned PDA at `[b"pool", mint]`, created by `complete` and seeded
+/// with the curve's real SOL reserve and liquidity tokens. The pool account
+/// holds the SOL reserve on top of its rent-exempt minimum, and its
+/// associated token account holds the token reserve. Shares are tracked by
+/// the LP mint at `[b"lp_mint", mint]`, whose mint authority is the pool.
+#[account]
+#[derive(Default)]
+pub struct Pool {
+    /// Token mint traded against SOL
+    pub mint: Pubkey,
+    /// LP mint representing shares of the reserves
+    pub lp_mint: Pubkey,
+    /// SOL reserve held by the pool account, in lamports
+    pub sol_reserve: u64,
+    /// Token reserve held by the pool's token account
+    pub token_reserve: u64,
+    /// LP tokens in circulation
+    pub lp_supply: u64,
+    /// Swap fee in basis points, taken from the input and left in the reserves
+    pub fee_bps: u16,
+    /// Timestamp when the pool was created
+    pub created_at: i64,
+    /// Bump seed for this pool PDA
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // mint
+        32 + // lp_mint
+        8 +  // sol_reserve
+        8 +  // token_reserve
+        8 +  // lp_supply
+        2 +  // fee_bps
+        8 +  // created_at
+        1;   // bump
+
+    /// Seed the pool and return the initial LP supply
+    /// Formula: lp_supply = floor(sqrt(sol_reserve * token_reserve))
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        mint: Pubkey,
+        lp_mint: Pubkey,
+        sol_reserve: u64,
+        token_reserve: u64,
+        fee_bps: u16,
+        bump: u8,
+        clock: &Clock,
+    ) -> Result<u64> {
+        let lp_supply = to_u64(isqrt(calculate_k(sol_reserve, token_reserve)))?;
+        require!(lp_supply > 0, PumpFunError::InsufficientPoolLiquidity);
+
+        self.mint = mint;
+        self.lp_mint = lp_mint;
+        self.sol_reserve = sol_reserve;
+        self.token_reserve = token_reserve;
+        self.lp_supply = lp_supply;
+        self.fee_bps = fee_bps;
+        self.created_at = clock.unix_timestamp;
+        self.bump = bump;
+        Ok(lp_supply)
+    }
+
+    /// Price a swap of `amount_in` as `(fee, amount_out)`
+    ///
+    /// The fee is taken from the input; the rest is priced with the same
+    /// constant-product formula as the curve (`calculate_tokens_out`, applied
+    /// to whichever reserves are in and out), rounded in the pool's favour.
+    pub fn quote_swap(&self, direction: SwapDirection, amount_in: u64) -> Result<(u64, u64)> {
+        let (reserve_in, reserve_out) = match direction {
+            SwapDirection::SolToToken => (self.sol_reserve, self.token_reserve),
+            SwapDirection::TokenToSol => (self.token_reserve, self.sol_reserve),
+        };
+
+        let (fee, net_in) = split_fee(amount_in, self.fee_bps)?;
+        let amount_out = calculate_tokens_out(net_in, reserve_in, reserve_out)?;
+
+        // Never empty a side of the pool
+        require!(amount_out < reserve_out, PumpFunError::InsufficientPoolLiquidity);
+        Ok((fee, amount_out))
+    }
+
+    /// Update reserves after a swap; the whole `amount_in`, fee included,
+    /// joins the input reserve
+    pub fn update_after_swap(
+        &mut self,
+        direction: SwapDirection,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> Result<()> {
+        let (sol_reserve, token_reserve) = match direction {
+            SwapDirection::SolToToken => (
+                self.sol_reserve.checked_add(amount_in).ok_or(PumpFunError::MathOverflow)?,
+                self.token_reserve
+                    .checked_sub(amount_out)
+                    .ok_or(PumpFunError::InsufficientPoolLiquidity)?,
+            ),
+            SwapDirection::TokenToSol => (
+                self.sol_reserve
+                    .checked_sub(amount_out)
+                    .ok_or(PumpFunError::InsufficientPoolLiquidity)?,
+                self.token_reserve.checked_add(amount_in).ok_or(PumpFunError::MathOverflow)?,
+            ),
+        };
+
+        self.sol_reserve = sol_reserve;
+        self.token_reserve = token_reserve;
+        Ok(())
+    }
+
+    /// Tokens and LP for a deposit of `sol_amount`, as `(tokens_in, lp_out)`
+    ///
+    /// Deposits keep the reserve ratio: tokens are rounded up and LP down.
+    /// Formula: tokens_in = ceil(sol_amount * token_reserve / sol_reserve)
+    ///          lp_out = floor(sol_amount * lp_supply / sol_reserve)
+    pub fn quote_deposit(&self, sol_amount: u64) -> Result<(u64, u64)> {
+        require!(sol_amount > 0, PumpFunError::InvalidAmount);
+        require!(self.sol_reserve > 0, PumpFunError::InvalidReserves);
+
+        let tokens_in = to_u64(div_ceil(
+            (sol_amount as u128) * (self.token_reserve as u128),
+            self.sol_reserve as u128,
+        )?)?;
+        let lp_out = to_u64(div_floor(
+            (sol_amount as u128) * (self.lp_supply as u128),
+            self.sol_reserve as u128,
+        )?)?;
+
+        require!(lp_out > 0, PumpFunError::InvalidAmount);
+        Ok((tokens_in, lp_out))
+    }
+
+    /// SOL and tokens paid for burning `lp_amount`, as `(sol_out, tokens_out)`
+    ///
+    /// Both are rounded down. The last LP tokens cannot be burned, so the
+    /// pool is never emptied.
+    /// Formula: sol_out = floor(lp_amount * sol_reserve / lp_supply)
+    ///          tokens_out = floor(lp_amount * token_reserve / lp_supply)
+    pub fn quote_withdrawal(&self, lp_amount: u64) -> Result<(u64, u64)> {
+        require!(lp_amount > 0, PumpFunError::InvalidAmount);
+        require!(lp_amount < self.lp_supply, PumpFunError::InsufficientPoolLiquidity);
+
+        let sol_out = to_u64(div_floor(
+            (lp_amount as u128) * (self.sol_reserve as u128),
+            self.lp_supply as u128,
+        )?)?;
+        let tokens_out = to_u64(div_floor(
+            (lp_amount as u128) * (self.token_reserve as u128),
+            self.lp_supply as u128,
+        )?)?;
+
+        Ok((sol_out, tokens_out))
+    }
+
+    /// Update reserves and LP supply after a deposit
+    pub fn update_after_deposit(&mut self, sol_in: u64, tokens_in: u64, lp_out: u64) -> Result<()> {
+        let sol_reserve = self.sol_reserve
+            .checked_add(sol_in)
+            .ok_or(PumpFunError::MathOverflow)?;
+        let token_reserve = self.token_reserve
+            .checked_add(tokens_in)
+            .ok_or(PumpFunError::MathOverflow)?;
+        let lp_supply = self.lp_supply
+            .checked_add(lp_out)
+            .ok_or(PumpFunError::MathOverflow)?;
+
+        self.sol_reserve = sol_reserve;
+        self.token_reserve = token_reserve;
+        self.lp_supply = lp_supply;
+        Ok(())
+    }
+
+    /// Update reserves and LP supply after a withdrawal
+    pub fn update_after_withdrawal(&mut self, sol_out: u64, tokens_out: u64, lp_in: u64) -> Result<()> {
+        let sol_reserve = self.sol_reserve
+            .checked_sub(sol_out)
+            .ok_or(PumpFunError::InsufficientPoolLiquidity)?;
+        let token_reserve = self.token_reserve
+            .checked_sub(tokens_out)
+            .ok_or(PumpFunError::InsufficientPoolLiquidity)?;
+        let lp_supply = self.lp_supply
+            .checked_sub(lp_in)
+            .ok_or(PumpFunError::InsufficientPoolLiquidity)?;
+
+        self.sol_reserve = sol_reserve;
+        self.token_reserve = token_reserve;
+        self.lp_supply = lp_supply;
+        Ok(())
+    }
+
+    /// Check that the pool account holds at least `sol_reserve` lamports on
+    /// top of its own rent-exempt minimum
+    pub fn assert_solvent(&self, pool: &AccountInfo) -> Result<()> {
+        let rent_exempt = Rent::get()?.minimum_balance(pool.data_len());
+        let available = pool
+            .lamports()
+            .checked_sub(rent_exempt)
+            .ok_or(PumpFunError::PoolInsolvent)?;
+
+        require!(
+            available >= self.sol_reserve,
+            PumpFunError::PoolInsolvent
+        );
+
+        Ok(())
+    }
+}
+
+/// Program-owned lock holding a pool's initial LP supply
+///
+/// PDA at `[b"lp_lock", mint]`, created by `complete`. The LP tokens sit in
+/// the lock's associated token account, and only `unlock_liquidity` can move
+/// them: to the creator, once, after `unlock_at`. A lock without `unlock_at`
+/// is permanent, so that liquidity can never be pulled.
+#[account]
+#[derive(Default)]
+pub struct LpLock {
+    /// Token mint of the pool
+    pub mint: Pubkey,
+    /// Pool whose LP tokens are locked
+    pub pool: Pubkey,
+    /// LP mint of the pool
+    pub lp_mint: Pubkey,
+    /// Creator who may unlock the LP tokens
+    pub beneficiary: Pubkey,
+    /// LP tokens locked at graduation
+    pub amount: u64,
+    /// Timestamp when the LP tokens were locked
+    pub locked_at: i64,
+    /// Timestamp from which the LP tokens can be unlocked, if ever
+    pub unlock_at: Option<i64>,
+    /// Whether the LP tokens have been released to the beneficiary
+    pub unlocked: bool,
+    /// Bump seed for this lock PDA
+    pub bump: u8,
+}
+
+impl LpLock {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // mint
+        32 + // pool
+        32 + // lp_mint
+        32 + // beneficiary
+        8 +  // amount
+        8 +  // locked_at
+        9 +  // unlock_at (Option<i64>)
+        1 +  // unlocked
+        1;   // bump
+
+    /// Check that the LP tokens can be released at `now`
+    pub fn assert_unlockable(&self, now: i64) -> Result<()> {
+        require!(!self.unlocked, PumpFunError::AlreadyUnlocked);
+        match self.unlock_at {
+            Some(unlock_at) if now >= unlock_at => Ok(()),
+            _ => err!(PumpFunError::LiquidityStillLocked),
+        }
+    }
 }
@@ -0,0 +1,175 @@
+//! `migrate_config` on a global config written in the original layout
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, Discriminator, InstructionData};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use pump_fun_clone::constants::*;
+use pump_fun_clone::errors::PumpFunError;
+use pump_fun_clone::migration::MigrationTarget;
+use pump_fun_clone::state::{GlobalConfig, GraduationCriterion, LegacyGlobalConfig};
+
+// Anchor's entry wants the account slice to live as long as its accounts
+fn pump_fun_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    pump_fun_clone::entry(program_id, accounts, data)
+}
+
+fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+struct Harness {
+    context: ProgramTestContext,
+    global_config: Pubkey,
+    legacy: LegacyGlobalConfig,
+}
+
+impl Harness {
+    /// Start with a legacy config owned by a fresh authority, as the original
+    /// program's `initialize` left it after some use
+    async fn start() -> (Self, Keypair) {
+        let mut program_test = ProgramTest::new(
+            "pump_fun_clone",
+            pump_fun_clone::ID,
+            processor!(pump_fun_entry),
+        );
+        let authority = Keypair::new();
+        let global_config = pda(&[b"global_config"], &pump_fun_clone::ID);
+        let (treasury, treasury_bump) = Pubkey::find_program_address(
+            &[b"treasury", global_config.as_ref()],
+            &pump_fun_clone::ID,
+        );
+        let legacy = LegacyGlobalConfig {
+            authority: authority.pubkey(),
+            treasury,
+            protocol_fee_bps: 75,
+            creation_fee: 30_000_000,
+            total_tokens_created: 42,
+            treasury_bump,
+        };
+
+        let mut data = GlobalConfig::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), LegacyGlobalConfig::SIZE);
+        program_test.add_account(
+            global_config,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: pump_fun_clone::ID,
+                ..Account::default()
+            },
+        );
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 1_000_000_000,
+                ..Account::default()
+            },
+        );
+
+        let context = program_test.start_with_context().await;
+        (Self { context, global_config, legacy }, authority)
+    }
+
+    async fn migrate_config(
+        &mut self,
+        authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: pump_fun_clone::ID,
+            accounts: pump_fun_clone::accounts::MigrateConfig {
+                authority: authority.pubkey(),
+                global_config: self.global_config,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: pump_fun_clone::instruction::MigrateConfig {}.data(),
+        };
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer, authority],
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn config_account(&mut self) -> Account {
+        self.context
+            .banks_client
+            .get_account(self.global_config)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+}
+
+fn assert_program_error(result: std::result::Result<(), BanksClientError>, error: PumpFunError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(error))
+        }
+        other => panic!("expected {error:?}, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn upgrades_a_legacy_config_once() {
+    let (mut harness, authority) = Harness::start().await;
+
+    harness.migrate_config(&authority).await.unwrap();
+
+    let account = harness.config_account().await;
+    assert_eq!(account.data.len(), GlobalConfig::SIZE);
+    assert!(account.lamports >= Rent::default().minimum_balance(GlobalConfig::SIZE));
+
+    // Legacy fields carry over
+    let config = GlobalConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+    let legacy = &harness.legacy;
+    assert_eq!(config.authority, legacy.authority);
+    assert_eq!(config.treasury, legacy.treasury);
+    assert_eq!(config.protocol_fee_bps, legacy.protocol_fee_bps);
+    assert_eq!(config.creation_fee, legacy.creation_fee);
+    assert_eq!(config.total_tokens_created, legacy.total_tokens_created);
+    assert_eq!(config.treasury_bump, legacy.treasury_bump);
+
+    // Newer fields start at their defaults
+    assert_eq!(config.pending_authority, None);
+    assert!(!config.paused);
+    assert_eq!(config.max_name_len, MAX_NAME_LEN);
+    assert_eq!(config.max_uri_len, MAX_URI_LEN);
+    assert_eq!(config.fees_collected, 0);
+    assert_eq!(config.enabled_curve_kinds, ALL_CURVE_KINDS);
+    assert_eq!(config.min_curve_params, DEFAULT_MIN_CURVE_PARAMS);
+    assert_eq!(config.max_curve_params, DEFAULT_MAX_CURVE_PARAMS);
+    assert_eq!(config.graduation_criterion, GraduationCriterion::RealSolRaised);
+    assert_eq!(config.pool_fee_bps, DEFAULT_POOL_FEE_BPS);
+    assert_eq!(config.migration_target, MigrationTarget::Pool);
+    assert_eq!(config.migration_fee, DEFAULT_MIGRATION_FEE);
+    assert_eq!(config.completer_reward, DEFAULT_COMPLETER_REWARD);
+
+    // A second run finds the current layout
+    let result = harness.migrate_config(&authority).await;
+    assert_program_error(result, PumpFunError::ConfigAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn rejects_a_signer_other_than_the_legacy_authority() {
+    let (mut harness, _) = Harness::start().await;
+    let impostor = Keypair::new();
+
+    let result = harness.migrate_config(&impostor).await;
+    assert_program_error(result, PumpFunError::Unauthorized);
+
+    let account = harness.config_account().await;
+    assert_eq!(account.data.len(), LegacyGlobalConfig::SIZE);
+}
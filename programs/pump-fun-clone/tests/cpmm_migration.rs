@@ -0,0 +1,349 @@
+//! `complete` through the `Cpmm` adapter, with `programs/mock-amm` loaded
+//! into program-test as the external venue
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::associated_token::{
+    self, get_associated_token_address_with_program_id, spl_associated_token_account,
+};
+use anchor_spl::token_2022;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use pump_fun_clone::constants::TOTAL_SUPPLY;
+use pump_fun_clone::curve::CurveKind;
+use pump_fun_clone::errors::PumpFunError;
+use pump_fun_clone::migration::MigrationTarget;
+use pump_fun_clone::state::{BondingCurve, CurveParams, GlobalConfig, LpLock};
+use pump_fun_clone::instructions::UpdateConfigParams;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+// Graduates at 1 SOL raised, keeping 600M tokens for liquidity
+const TIER: CurveParams = CurveParams {
+    initial_virtual_sol_reserve: 5 * LAMPORTS_PER_SOL,
+    initial_virtual_token_reserve: 500_000_000_000_000,
+    graduation_threshold: LAMPORTS_PER_SOL,
+    token_allocation: 400_000_000_000_000,
+};
+
+// Anchor's entry wants the account slice to live as long as its accounts
+fn pump_fun_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    pump_fun_clone::entry(program_id, accounts, data)
+}
+
+fn mock_amm_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_amm::entry(program_id, accounts, data)
+}
+
+fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &token_2022::ID)
+}
+
+/// Addresses of one curve and of its venue pool
+struct Curve {
+    mint: Keypair,
+    bonding_curve: Pubkey,
+    curve_vault: Pubkey,
+    curve_token_account: Pubkey,
+    lp_lock: Pubkey,
+    migration_escrow: Pubkey,
+    amm_pool: Pubkey,
+    amm_lp_mint: Pubkey,
+    amm_token_vault: Pubkey,
+    lock_lp_account: Pubkey,
+}
+
+impl Curve {
+    fn new() -> Self {
+        let mint = Keypair::new();
+        let key = mint.pubkey();
+        let bonding_curve = pda(&[b"bonding_curve", key.as_ref()], &pump_fun_clone::ID);
+        let lp_lock = pda(&[b"lp_lock", key.as_ref()], &pump_fun_clone::ID);
+        let amm_pool = pda(&[b"pool", key.as_ref()], &mock_amm::ID);
+        let amm_lp_mint = pda(&[b"lp_mint", amm_pool.as_ref()], &mock_amm::ID);
+        Self {
+            bonding_curve,
+            curve_vault: pda(&[b"curve_vault", key.as_ref()], &pump_fun_clone::ID),
+            curve_token_account: ata(&bonding_curve, &key),
+            lp_lock,
+            migration_escrow: pda(&[b"migration_escrow", key.as_ref()], &pump_fun_clone::ID),
+            amm_pool,
+            amm_lp_mint,
+            amm_token_vault: ata(&amm_pool, &key),
+            lock_lp_account: ata(&lp_lock, &amm_lp_mint),
+            mint,
+        }
+    }
+}
+
+struct Harness {
+    context: ProgramTestContext,
+    global_config: Pubkey,
+    treasury: Pubkey,
+}
+
+impl Harness {
+    /// Initialize the protocol with `MigrationTarget::Cpmm` at the mock AMM
+    async fn start() -> Self {
+        let mut program_test = ProgramTest::new(
+            "pump_fun_clone",
+            pump_fun_clone::ID,
+            processor!(pump_fun_entry),
+        );
+        program_test.add_program("mock_amm", mock_amm::ID, processor!(mock_amm_entry));
+        let context = program_test.start_with_context().await;
+
+        let global_config = pda(&[b"global_config"], &pump_fun_clone::ID);
+        let treasury = pda(&[b"treasury", global_config.as_ref()], &pump_fun_clone::ID);
+        let mut harness = Self { context, global_config, treasury };
+        let authority = harness.context.payer.pubkey();
+
+        harness
+            .send(
+                pump_fun_clone::accounts::Initialize {
+                    authority,
+                    global_config,
+                    treasury,
+                    system_program: system_program::ID,
+                },
+                pump_fun_clone::instruction::Initialize { authority },
+                &[],
+            )
+            .await
+            .unwrap();
+        harness
+            .send(
+                pump_fun_clone::accounts::UpdateConfig { authority, global_config },
+                pump_fun_clone::instruction::UpdateConfig {
+                    params: UpdateConfigParams {
+                        migration_target: Some(MigrationTarget::Cpmm { program_id: mock_amm::ID }),
+                        ..Default::default()
+                    },
+                },
+                &[],
+            )
+            .await
+            .unwrap();
+
+        harness
+    }
+
+    async fn send_instructions(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn send(
+        &mut self,
+        accounts: impl ToAccountMetas,
+        data: impl InstructionData,
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: pump_fun_clone::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        };
+        self.send_instructions(&[instruction], signers).await
+    }
+
+    async fn fetch<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context.banks_client.get_balance(address).await.unwrap()
+    }
+
+    async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        // Token-2022 accounts share the SPL Token base layout: amount at 64..72
+        u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+    }
+
+    /// Create a curve on the test tier and buy past its graduation threshold
+    async fn graduated_curve(&mut self) -> Curve {
+        let curve = Curve::new();
+        let payer = self.context.payer.pubkey();
+        let mint = curve.mint.insecure_clone();
+
+        self.send(
+            pump_fun_clone::accounts::Create {
+                creator: payer,
+                mint: mint.pubkey(),
+                bonding_curve: curve.bonding_curve,
+                curve_vault: curve.curve_vault,
+                curve_token_account: curve.curve_token_account,
+                global_config: self.global_config,
+                treasury: self.treasury,
+                token_program: token_2022::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            pump_fun_clone::instruction::Create {
+                name: "Pool".to_string(),
+                symbol: "POOL".to_string(),
+                uri: "https://example.com/metadata.json".to_string(),
+                curve_kind: CurveKind::ConstantProduct,
+                curve_params: TIER,
+                lp_unlock_delay: None,
+            },
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        let create_ata = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            &payer,
+            &mint.pubkey(),
+            &token_2022::ID,
+        );
+        let buy = Instruction {
+            program_id: pump_fun_clone::ID,
+            accounts: pump_fun_clone::accounts::Buy {
+                buyer: payer,
+                bonding_curve: curve.bonding_curve,
+                mint: mint.pubkey(),
+                buyer_token_account: ata(&payer, &mint.pubkey()),
+                curve_token_account: curve.curve_token_account,
+                curve_vault: curve.curve_vault,
+                global_config: self.global_config,
+                treasury: self.treasury,
+                token_program: token_2022::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: pump_fun_clone::instruction::Buy {
+                sol_in: 2 * LAMPORTS_PER_SOL,
+                min_tokens_out: 0,
+            }
+            .data(),
+        };
+        self.send_instructions(&[create_ata, buy], &[]).await.unwrap();
+
+        curve
+    }
+
+    /// `complete` with the Cpmm adapter's accounts, `venue_program` first
+    async fn complete(
+        &mut self,
+        curve: &Curve,
+        venue_program: Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let mut accounts = pump_fun_clone::accounts::Complete {
+            completer: self.context.payer.pubkey(),
+            bonding_curve: curve.bonding_curve,
+            mint: curve.mint.pubkey(),
+            curve_token_account: curve.curve_token_account,
+            curve_vault: curve.curve_vault,
+            pool: None,
+            lp_mint: None,
+            pool_token_account: None,
+            lp_lock: curve.lp_lock,
+            lock_lp_account: None,
+            migration_escrow: Some(curve.migration_escrow),
+            global_config: self.global_config,
+            treasury: self.treasury,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend([
+            AccountMeta::new_readonly(venue_program, false),
+            AccountMeta::new(curve.amm_pool, false),
+            AccountMeta::new(curve.amm_token_vault, false),
+            AccountMeta::new(curve.amm_lp_mint, false),
+            AccountMeta::new(curve.lock_lp_account, false),
+        ]);
+        let instruction = Instruction {
+            program_id: pump_fun_clone::ID,
+            accounts,
+            data: pump_fun_clone::instruction::Complete {}.data(),
+        };
+        self.send_instructions(&[instruction], &[]).await
+    }
+}
+
+fn assert_program_error(result: std::result::Result<(), BanksClientError>, error: PumpFunError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(error))
+        }
+        other => panic!("expected {error:?}, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn migrates_into_the_configured_amm() {
+    let mut harness = Harness::start().await;
+    let curve = harness.graduated_curve().await;
+
+    let graduated: BondingCurve = harness.fetch(curve.bonding_curve).await;
+    let config: GlobalConfig = harness.fetch(harness.global_config).await;
+    let (migration_fee, _) = config.migration_fee_split(graduated.real_sol_reserve);
+    let sol_amount = graduated.real_sol_reserve - migration_fee;
+    let token_amount = TOTAL_SUPPLY - TIER.token_allocation;
+
+    harness.complete(&curve, mock_amm::ID).await.unwrap();
+
+    // The venue holds exactly the migrated liquidity
+    let pool: mock_amm::AmmPool = harness.fetch(curve.amm_pool).await;
+    assert_eq!(pool.sol_reserve, sol_amount);
+    assert_eq!(pool.token_reserve, token_amount);
+    assert_eq!(pool.lp_mint, curve.amm_lp_mint);
+    assert_eq!(harness.token_balance(curve.amm_token_vault).await, token_amount);
+    assert_eq!(harness.lamports(curve.migration_escrow).await, 0);
+
+    // Its LP supply is held by the curve's lock
+    let lock: LpLock = harness.fetch(curve.lp_lock).await;
+    assert_eq!(lock.pool, curve.amm_pool);
+    assert_eq!(lock.lp_mint, curve.amm_lp_mint);
+    assert_eq!(lock.amount, pool.lp_supply);
+    assert_eq!(harness.token_balance(curve.lock_lp_account).await, pool.lp_supply);
+
+    let migrated: BondingCurve = harness.fetch(curve.bonding_curve).await;
+    assert!(migrated.migrated);
+    assert_eq!(migrated.real_sol_reserve, 0);
+    assert_eq!(migrated.migrated_sol, sol_amount);
+    assert_eq!(migrated.migrated_tokens, token_amount);
+    assert_eq!(harness.token_balance(curve.curve_token_account).await, 0);
+}
+
+#[tokio::test]
+async fn rejects_a_venue_other_than_the_configured_one() {
+    let mut harness = Harness::start().await;
+    let curve = harness.graduated_curve().await;
+
+    let result = harness.complete(&curve, token_2022::ID).await;
+    assert_program_error(result, PumpFunError::InvalidMigrationTarget);
+
+    let curve_state: BondingCurve = harness.fetch(curve.bonding_curve).await;
+    assert!(!curve_state.migrated);
+}